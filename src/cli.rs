@@ -0,0 +1,244 @@
+use std::path::PathBuf;
+
+use bevy::{app::ScheduleRunnerPlugin, ecs::system::RunSystemOnce, prelude::*};
+
+use crate::{
+    designer::{
+        design_check::{check_design, DesignCheckReport},
+        devices::register_devices,
+        model::ModelRegistry,
+        save_management::replace_board,
+    },
+    events::{LoadEvent, SpawnDeviceEvent},
+};
+
+pub const USAGE: &str = "\
+Usage: logics [FILE] [OPTIONS]
+
+Arguments:
+  [FILE]            Board to open on startup
+
+Options:
+  --speed <FACTOR>  Initial simulation speed, 1.0 is realtime
+  --paused          Start with the simulation paused
+  --check           Load FILE, run the design check, print all problems and exit,
+                    overlapping devices are not checked
+  -h, --help        Print this help";
+
+/// Options passed on the command line.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct CliArgs {
+    pub file: Option<PathBuf>,
+    pub speed: Option<f32>,
+    pub paused: bool,
+    /// Load the file, check it and exit without user interaction.
+    pub check: bool,
+    pub help: bool,
+}
+
+impl CliArgs {
+    /// Parses the arguments without the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut cli_args = CliArgs::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--speed" => {
+                    let speed = args
+                        .next()
+                        .ok_or("--speed needs a value")?
+                        .parse::<f32>()
+                        .map_err(|error| format!("invalid value for --speed: {}", error))?;
+
+                    if !speed.is_finite() || speed <= 0.0 {
+                        return Err("--speed must be a positive number".into());
+                    }
+
+                    cli_args.speed = Some(speed);
+                }
+                "--paused" => cli_args.paused = true,
+                "--check" => cli_args.check = true,
+                "-h" | "--help" => cli_args.help = true,
+                _ if arg.starts_with('-') => return Err(format!("unknown option {}", arg)),
+                _ if cli_args.file.is_some() => {
+                    return Err(format!("unexpected argument {}", arg));
+                }
+                _ => cli_args.file = Some(PathBuf::from(arg)),
+            }
+        }
+
+        if cli_args.check && cli_args.file.is_none() {
+            return Err("--check needs a FILE".into());
+        }
+
+        Ok(cli_args)
+    }
+
+    /// Makes the file path absolute, so it still works as a recent file when the app is started
+    /// from another directory.
+    pub fn resolve_file(mut self) -> Result<Self, String> {
+        if let Some(file) = &self.file {
+            let resolved = file
+                .canonicalize()
+                .map_err(|error| format!("cannot open {}: {}", file.display(), error))?;
+            self.file = Some(resolved);
+        }
+
+        Ok(self)
+    }
+}
+
+pub struct CliPlugin(pub CliArgs);
+
+impl Plugin for CliPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.0.clone())
+            .add_systems(Startup, apply_cli_args);
+    }
+}
+
+/// Opens the file and configures the simulation as requested on the command line.
+fn apply_cli_args(
+    cli_args: Res<CliArgs>,
+    mut time: ResMut<Time<Virtual>>,
+    mut load_ev_writer: EventWriter<LoadEvent>,
+) {
+    if let Some(file) = &cli_args.file {
+        load_ev_writer.send(LoadEvent { path: file.clone() });
+    }
+
+    if let Some(speed) = cli_args.speed {
+        time.set_relative_speed(speed);
+    }
+
+    if cli_args.paused {
+        time.pause();
+    }
+}
+
+/// Runs the design check without a window or renderer, so it works on machines without a display.
+/// Only the models are loaded, so devices are not checked for overlaps, which needs their views.
+/// This is printed with the results, so nobody takes the check for complete.
+/// Nothing is written, neither the session lock, recovery files nor the recent files.
+pub fn run_headless_check(cli_args: CliArgs) -> AppExit {
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins.set(ScheduleRunnerPlugin::run_once()))
+        .add_event::<SpawnDeviceEvent>()
+        .init_resource::<ModelRegistry>()
+        .init_resource::<DesignCheckReport>()
+        .insert_resource(cli_args)
+        .add_systems(Startup, run_check);
+
+    register_devices(&mut app);
+
+    app.run()
+}
+
+/// Loads the file, runs the design check, prints the results and exits.
+/// Exits with an error code if the file could not be loaded, had to be repaired or has issues.
+fn run_check(world: &mut World) {
+    let Some(path) = world.resource::<CliArgs>().file.clone() else {
+        return;
+    };
+
+    let mut problems = 0;
+
+    match replace_board(world, &path) {
+        Ok(repairs) => {
+            for repair in repairs.iter() {
+                println!("repaired: {}", repair);
+            }
+            problems += repairs.len();
+
+            if let Err(error) = world.run_system_once(check_design) {
+                println!("error: {}", error);
+                problems += 1;
+            }
+
+            for issue in world.resource::<DesignCheckReport>().issues.iter() {
+                println!("issue: {}", issue.message);
+                problems += 1;
+            }
+        }
+        Err(error) => {
+            println!("error: {}", error);
+            problems += 1;
+        }
+    }
+
+    println!("skipped: overlapping devices are only checked in the designer");
+
+    match problems {
+        0 => {
+            println!("No problems found.");
+            world.send_event(AppExit::Success);
+        }
+        _ => {
+            println!("{} problem(s) found.", problems);
+            world.send_event(AppExit::error());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        CliArgs::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_file_and_options() {
+        assert_eq!(
+            parse(&["board.logics", "--speed", "2.5", "--paused"]),
+            Ok(CliArgs {
+                file: Some(PathBuf::from("board.logics")),
+                speed: Some(2.5),
+                paused: true,
+                ..default()
+            })
+        );
+    }
+
+    #[test]
+    fn parses_no_arguments() {
+        assert_eq!(parse(&[]), Ok(CliArgs::default()));
+    }
+
+    #[test]
+    fn rejects_invalid_speeds() {
+        assert!(parse(&["--speed"]).is_err());
+        assert!(parse(&["--speed", "fast"]).is_err());
+        assert!(parse(&["--speed", "0"]).is_err());
+        assert!(parse(&["--speed", "-1"]).is_err());
+        assert!(parse(&["--speed", "inf"]).is_err());
+    }
+
+    #[test]
+    fn rejects_unknown_options_and_extra_files() {
+        assert!(parse(&["--fast"]).is_err());
+        assert!(parse(&["a.logics", "b.logics"]).is_err());
+    }
+
+    #[test]
+    fn check_needs_a_file() {
+        assert!(parse(&["--check"]).is_err());
+        assert!(parse(&["--check", "board.logics"]).is_ok_and(|cli_args| cli_args.check));
+    }
+
+    #[test]
+    fn resolves_the_file_to_an_absolute_path() {
+        let cli_args = parse(&["Cargo.toml"])
+            .and_then(CliArgs::resolve_file)
+            .unwrap();
+        assert!(cli_args.file.is_some_and(|file| file.is_absolute()));
+
+        assert!(parse(&["missing.logics"])
+            .and_then(CliArgs::resolve_file)
+            .is_err());
+        assert_eq!(CliArgs::default().resolve_file(), Ok(CliArgs::default()));
+    }
+}
@@ -1,237 +1,174 @@
-use std::collections::HashMap;
+use bevy::{prelude::*, scene::ron};
 
-use bevy::prelude::*;
-use moonshine_save::save::Save;
-use uuid::Uuid;
-
-use crate::events::{CopyEvent, PasteEvent};
+use crate::events::{CopyEvent, DuplicateEvent, PasteEvent};
 
 use super::{
-    devices::device::DeviceModel,
-    model::ModelId,
-    pin::PinModelCollection,
+    cursor::{Cursor, CursorState},
     position::Position,
-    selection::Selected,
-    wire::{
-        wire_joint::{WireJointModel, WireJointModelBundle},
-        WireModelBundle, WireNode, WireNodes,
+    save_management::{
+        format::{collect_selection, spawn_board, BoardFile},
+        migration::read_board,
     },
+    selection::{Dragged, Selected},
 };
 
-//TODO:
-// maybe refactor by only having one copy and one paste function
-// and a pipeline for update_position, update_model_uuids, init_drag etc... just passing the spawned entity ids
+/// Offset of duplicated models to the originals, so both stay visible.
+const DUPLICATE_OFFSET: Vec2 = Vec2::new(50.0, -50.0);
 
 pub struct CopyPastePlugin;
 
 impl Plugin for CopyPastePlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<DeviceClipboard>()
-            .init_resource::<WireClipboard>()
-            .init_resource::<WireJointClipboard>()
-            .add_systems(
-                Update,
-                (copy_devices, copy_wire_joints, copy_wires).run_if(on_event::<CopyEvent>),
-            )
+        app.insert_non_send_resource(BoardClipboard::new())
+            .add_systems(Update, copy_selection.run_if(on_event::<CopyEvent>))
+            .add_systems(Update, paste_clipboard.run_if(on_event::<PasteEvent>))
             .add_systems(
                 Update,
-                (paste_devices.pipe(paste_wire_joints).pipe(paste_wires))
-                    .run_if(on_event::<PasteEvent>),
+                duplicate_selection.run_if(on_event::<DuplicateEvent>),
             );
     }
 }
 
-/// Stores all components from all devices after copying using reflect.
-#[derive(Resource, Default)]
-pub struct DeviceClipboard {
-    pub items: Vec<Vec<Box<dyn PartialReflect>>>,
+/// Holds copied selections as text in the save format, so they can be pasted into other instances.
+/// Uses the system clipboard and falls back to an in-memory copy if there is none, e.g. on headless machines.
+pub struct BoardClipboard {
+    system_clipboard: Option<arboard::Clipboard>,
+    fallback: String,
 }
 
-/// Copies all devices and stores them in the [`DeviceClipboard`].
-/// Currently copies all components on the device entity
-/// that are registered in the type registry and can be reflected.
-pub fn copy_devices(world: &mut World) {
-    let entities: Vec<Entity> = world
-        .query_filtered::<Entity, (With<Selected>, With<DeviceModel>)>()
-        .iter(world)
-        .collect();
+impl BoardClipboard {
+    fn new() -> Self {
+        let system_clipboard = arboard::Clipboard::new()
+            .inspect_err(|error| warn!("No system clipboard, copying in memory only: {}", error))
+            .ok();
 
-    let mut copied_devices: Vec<Vec<Box<dyn PartialReflect>>> = Vec::new();
-
-    {
-        let type_registry = world.resource::<AppTypeRegistry>().read();
-        for &entity in entities.iter() {
-            let entity_ref = world.entity(entity);
-            let device_components: Vec<Box<dyn PartialReflect>> = entity_ref
-                .archetype()
-                .components()
-                .filter_map(|component_id| {
-                    let type_id = world
-                        .components()
-                        .get_info(component_id)
-                        .unwrap()
-                        .type_id()
-                        .unwrap();
-
-                    type_registry.get(type_id).map(|type_registration| {
-                        type_registration
-                            .data::<ReflectComponent>()
-                            .unwrap()
-                            .reflect(entity_ref)
-                            .unwrap()
-                            .clone_value()
-                    })
-                })
-                .collect();
-
-            copied_devices.push(device_components);
+        Self {
+            system_clipboard,
+            fallback: String::new(),
         }
     }
 
-    world.resource_mut::<DeviceClipboard>().items = copied_devices;
-}
+    pub fn set_text(&mut self, text: String) {
+        if let Some(system_clipboard) = self.system_clipboard.as_mut() {
+            if let Err(error) = system_clipboard.set_text(text.clone()) {
+                warn!("Failed to write to the system clipboard: {}", error);
+            }
+        }
 
-/// Spawns all devices stored in the [`DeviceClipboard`]
-/// and selects them while deselecting all other currently selected devices.
-pub fn paste_devices(world: &mut World) -> HashMap<Uuid, Uuid> {
-    let mut q_selected = world.query_filtered::<Entity, With<Selected>>();
-    let selected_entities: Vec<Entity> = q_selected.iter(world).collect();
-    for entity in selected_entities {
-        world.entity_mut(entity).remove::<Selected>();
+        self.fallback = text;
     }
 
-    let registry = world.resource::<AppTypeRegistry>().clone();
-    let type_registry = registry.read();
-    let mut spawned_entities: Vec<Entity> = Vec::new();
-
-    world.resource_scope(|world, clipboard: Mut<DeviceClipboard>| {
-        for clipboard_item in clipboard.items.iter() {
-            let entity_mut = &mut world.spawn((Selected, Save)); // HACK: manually insert save because it does not implement the reflect trait and is not copied
-            spawned_entities.push(entity_mut.id());
-            for component in clipboard_item.iter() {
-                let type_info = component.get_represented_type_info().unwrap();
-                let registration = type_registry.get(type_info.type_id()).unwrap();
-                let reflect_component = registration.data::<ReflectComponent>().unwrap();
-                reflect_component.apply_or_insert(entity_mut, &**component, &type_registry);
-            }
-        }
-    });
+    pub fn get_text(&mut self) -> String {
+        self.system_clipboard
+            .as_mut()
+            .and_then(|system_clipboard| system_clipboard.get_text().ok())
+            .unwrap_or_else(|| self.fallback.clone())
+    }
+}
 
-    // let mut q_cursor = world.query::<&Cursor>();
-    // let cursor = q_cursor
-    //     .get_single(world)
-    //     .expect("No cursor entity in the scene.");
+/// Writes the selected devices, wire joints and wires to the [`BoardClipboard`].
+pub fn copy_selection(world: &mut World) {
+    let board = collect_selection(world);
 
-    //TODO: spawn relative to cursor
-    for &entity in spawned_entities.iter() {
-        let mut position = world
-            .query::<&mut Position>()
-            .get_mut(world, entity)
-            .unwrap();
+    if board.devices.is_empty() && board.wire_joints.is_empty() {
+        return;
+    }
 
-        position.0 += Vec2::new(50.0, -50.0);
+    match ron::ser::to_string_pretty(&board, ron::ser::PrettyConfig::default()) {
+        Ok(text) => world
+            .non_send_resource_mut::<BoardClipboard>()
+            .set_text(text),
+        Err(error) => error!("Failed to copy the selection: {}", error),
     }
+}
 
-    // Randomize pin uuids and map old uuids to new uuids
-    let mut pin_uuid_mapping: HashMap<Uuid, Uuid> = HashMap::new();
+/// Spawns the board stored in the [`BoardClipboard`] with new ids centered on the cursor,
+/// selects it and attaches it to the cursor until it is placed with a click.
+/// Text that is not a board in the save format is ignored.
+pub fn paste_clipboard(world: &mut World) {
+    let text = world.non_send_resource_mut::<BoardClipboard>().get_text();
+
+    let board = match read_board(text.as_bytes()) {
+        Ok(board) => board,
+        Err(error) => {
+            info!("Clipboard does not contain a board: {}", error);
+            return;
+        }
+    };
 
-    for &entity in spawned_entities.iter() {
-        let mut pin_model_collection = world
-            .query::<&mut PinModelCollection>()
-            .get_mut(world, entity)
-            .unwrap();
+    let cursor_position = get_cursor_position(world);
+    let mut board = board.with_fresh_ids();
+    board.translate(cursor_position - board.center());
 
-        let device_pin_uuids: Vec<Uuid> = pin_model_collection
-            .iter()
-            .map(|pin_model| pin_model.uuid)
-            .collect();
+    let pasted_entities = paste_board(world, &board);
+    attach_to_cursor(world, &pasted_entities, cursor_position);
+}
 
-        pin_model_collection.randomize_pin_uuids();
+/// Clones the selection including the wires and wire joints between the selected devices
+/// and places the copy next to it.
+pub fn duplicate_selection(world: &mut World) {
+    let board = collect_selection(world);
 
-        pin_model_collection.iter().zip(device_pin_uuids).for_each(
-            |(new_pin_model, old_device_pin_uuid)| {
-                pin_uuid_mapping.insert(old_device_pin_uuid, new_pin_model.uuid);
-            },
-        );
+    if board.devices.is_empty() && board.wire_joints.is_empty() {
+        return;
     }
 
-    pin_uuid_mapping
-}
+    let mut board = board.with_fresh_ids();
+    board.translate(DUPLICATE_OFFSET);
 
-/// Stores all wires after copying.
-#[derive(Resource, Default)]
-pub struct WireClipboard {
-    pub items: Vec<WireNodes>,
+    paste_board(world, &board);
 }
 
-/// Copies all wires and stores them in the [`WireClipboard`].
-pub fn copy_wires(
-    q_wires: Query<&WireNodes, With<Selected>>,
-    mut wire_clipboard: ResMut<WireClipboard>,
-) {
-    wire_clipboard.items.clear();
-    wire_clipboard.items = q_wires.iter().cloned().collect();
-}
+/// Spawns the board and makes it the only selection.
+/// Returns the spawned entities.
+pub fn paste_board(world: &mut World, board: &BoardFile) -> Vec<Entity> {
+    let selected_entities: Vec<Entity> = world
+        .query_filtered::<Entity, With<Selected>>()
+        .iter(world)
+        .collect();
+    for entity in selected_entities {
+        world.entity_mut(entity).remove::<Selected>();
+    }
 
-/// Spawns all wires stored in the [`WireClipboard`]
-/// and updates the pin uuids to match the pasted devices.
-pub fn paste_wires(
-    In(uuid_mapping): In<HashMap<Uuid, Uuid>>,
-    mut commands: Commands,
-    wire_clipboard: Res<WireClipboard>,
-) {
-    for wire_nodes in wire_clipboard.items.iter() {
-        // updates the uuids to the mapped ones or discards the wire if a mapping doesnt exist
-        let new_wire_nodes: Vec<WireNode> = wire_nodes
-            .0
-            .iter()
-            .filter_map(|wire_node| match wire_node {
-                WireNode::Joint(uuid) => uuid_mapping
-                    .get(uuid)
-                    .map(|&mapped_uuid| WireNode::Joint(mapped_uuid)),
-                WireNode::Pin(uuid) => uuid_mapping
-                    .get(uuid)
-                    .map(|&mapped_uuid| WireNode::Pin(mapped_uuid)),
-            })
-            .collect();
-
-        commands.spawn((WireModelBundle::new(new_wire_nodes), Selected));
+    let spawned_board = spawn_board(world, board);
+    for repair in spawned_board.repairs.iter() {
+        warn!("{}", repair);
     }
-}
 
-/// Stores all wire joints after copying.
-#[derive(Resource, Default)]
-pub struct WireJointClipboard {
-    pub items: Vec<(Position, Uuid)>,
-}
+    for &entity in spawned_board.entities.iter() {
+        world.entity_mut(entity).insert(Selected);
+    }
 
-/// Copies all wire joints and stores them in the [`WireJointClipboard`].
-#[allow(clippy::type_complexity)]
-pub fn copy_wire_joints(
-    q_wire_joints: Query<(&Position, &ModelId), (With<Selected>, With<WireJointModel>)>,
-    mut wire_joint_clipboard: ResMut<WireJointClipboard>,
-) {
-    wire_joint_clipboard.items.clear();
-    wire_joint_clipboard.items = q_wire_joints
-        .iter()
-        .map(|(position, model_id)| (position.clone(), model_id.0))
-        .collect();
+    spawned_board.entities
 }
 
-/// Spawns all wire joints stored in the [`WireJointClipboard`]
-pub fn paste_wire_joints(
-    In(uuid_mapping): In<HashMap<Uuid, Uuid>>,
-    wire_joint_clipboard: Res<WireJointClipboard>,
-    mut commands: Commands,
-) -> HashMap<Uuid, Uuid> {
-    let mut uuid_mapping: HashMap<Uuid, Uuid> = uuid_mapping;
-
-    for (joint_position, joint_uuid) in wire_joint_clipboard.items.iter() {
-        let joint_position = Position(joint_position.0 + Vec2::new(50.0, -50.0));
-        let new_joint = WireJointModelBundle::new(joint_position);
-        uuid_mapping.insert(*joint_uuid, new_joint.model.id.0);
-        commands.spawn((new_joint, Selected));
+/// Drags the entities with the cursor, keeping their offset to the cursor position.
+pub fn attach_to_cursor(world: &mut World, entities: &[Entity], cursor_position: Vec2) {
+    let Ok(mut cursor) = world.query::<&mut Cursor>().get_single_mut(world) else {
+        return;
+    };
+
+    // something else is already attached to the cursor
+    if cursor.state != CursorState::Idle {
+        return;
     }
 
-    uuid_mapping
+    cursor.state = CursorState::Dragging;
+
+    for &entity in entities {
+        let mut entity_mut = world.entity_mut(entity);
+
+        if let Some(position) = entity_mut.get::<Position>() {
+            let cursor_offset = Position(position.0 - cursor_position);
+            entity_mut.insert(Dragged { cursor_offset });
+        }
+    }
+}
+
+fn get_cursor_position(world: &mut World) -> Vec2 {
+    world
+        .query_filtered::<&Transform, With<Cursor>>()
+        .get_single(world)
+        .map(|transform| transform.translation.truncate())
+        .unwrap_or_default()
 }
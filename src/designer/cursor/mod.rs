@@ -7,6 +7,7 @@ use super::{
     devices::{
         binary_io::{BinaryDisplayPin, BinarySwitchPin},
         generic_chip::{GenericChip, GenericChipInputPin, GenericChipOutputPin},
+        net_label::NetLabelPin,
     },
     render_settings::CircuitBoardRenderingSettings,
 };
@@ -93,6 +94,7 @@ pub fn highlight_hovered_pin(
             With<GenericChipOutputPin>,
             With<BinarySwitchPin>,
             With<BinaryDisplayPin>,
+            With<NetLabelPin>,
         )>,
     >,
     render_settings: Res<CircuitBoardRenderingSettings>,
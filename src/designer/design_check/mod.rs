@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use moonshine_view::{View, Viewable};
+
+use crate::events::{CheckDesignEvent, FocusEntityEvent};
+
+use super::{
+    bounding_box::BoundingBox,
+    devices::{
+        device::{
+            DeviceCategory, DeviceIds, DeviceModel, DeviceSerializers, DeviceView, DeviceViewKind,
+        },
+        drive::OutputDrive,
+        generic_chip::GenericChip,
+        net_label::NetLabel,
+    },
+    model::{ModelId, ModelRegistry},
+    pin::{PinModelCollection, PinType},
+    position::Position,
+    selection::Selected,
+    wire::{routing::WireRoute, wire_joint::WireJointModel, WireModel, WireNode, WireNodes},
+};
+
+/// Read-only checks that look for common mistakes on the board.
+pub struct DesignCheckPlugin;
+
+impl Plugin for DesignCheckPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DesignCheckReport>()
+            .add_systems(Update, check_design.run_if(on_event::<CheckDesignEvent>))
+            .add_systems(Update, focus_entity.run_if(on_event::<FocusEntityEvent>));
+    }
+}
+
+pub struct DesignIssue {
+    pub message: String,
+    pub entity: Entity,
+    /// Board position the camera is moved to when the issue is focused.
+    pub position: Vec2,
+}
+
+/// Results of the last design check.
+#[derive(Resource, Default)]
+pub struct DesignCheckReport {
+    pub issues: Vec<DesignIssue>,
+    pub open: bool,
+}
+
+/// Walks the model graph and collects all found issues into the [`DesignCheckReport`].
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn check_design(
+    q_devices: Query<
+        (
+            Entity,
+            &Position,
+            &PinModelCollection,
+            Option<&GenericChip>,
+            Option<&NetLabel>,
+        ),
+        With<DeviceModel>,
+    >,
+    q_device_views: Query<(&View<DeviceViewKind>, &BoundingBox), With<DeviceView>>,
+    q_output_drives: Query<&OutputDrive>,
+    // wires only have views when the check runs in the designer
+    q_wires: Query<(Entity, &WireNodes, Option<&Viewable<WireModel>>), With<WireModel>>,
+    q_wire_routes: Query<&WireRoute>,
+    q_wire_joints: Query<(Entity, &ModelId, &Position), With<WireJointModel>>,
+    q_device_refs: Query<EntityRef, With<DeviceModel>>,
+    model_registry: Res<ModelRegistry>,
+    device_serializers: Res<DeviceSerializers>,
+    device_ids: Res<DeviceIds>,
+    mut report: ResMut<DesignCheckReport>,
+) {
+    let mut issues: Vec<DesignIssue> = Vec::new();
+
+    let wire_position = |viewable: Option<&Viewable<WireModel>>| {
+        viewable
+            .and_then(|viewable| q_wire_routes.get(viewable.view().entity()).ok())
+            .and_then(|route| route.points.first().copied())
+            .unwrap_or_default()
+    };
+
+    // wires referencing models that dont exist
+    for (wire_entity, wire_nodes, wire_viewable) in q_wires.iter() {
+        for wire_node in wire_nodes.0.iter() {
+            let exists = match wire_node {
+                WireNode::Joint(joint_uuid) => {
+                    model_registry.try_get_model_entity(joint_uuid).is_some()
+                }
+                WireNode::Pin(pin_uuid) => q_devices
+                    .iter()
+                    .any(|(_, _, pins, _, _)| pins.get_model(*pin_uuid).is_some()),
+            };
+
+            if !exists {
+                issues.push(DesignIssue {
+                    message: format!("Wire references missing {}.", describe_wire_node(wire_node)),
+                    entity: wire_entity,
+                    position: wire_position(wire_viewable),
+                });
+            }
+        }
+    }
+
+    // wire joints that are not part of any wire
+    for (joint_entity, joint_id, joint_position) in q_wire_joints.iter() {
+        let is_used = q_wires
+            .iter()
+            .any(|(_, wire_nodes, _)| wire_nodes.0.contains(&WireNode::Joint(joint_id.0)));
+
+        if !is_used {
+            issues.push(DesignIssue {
+                message: "Wire joint is not connected to any wire.".into(),
+                entity: joint_entity,
+                position: joint_position.0,
+            });
+        }
+    }
+
+    // group all pins into nets
+    let mut parents: HashMap<WireNode, WireNode> = HashMap::new();
+
+    for (_, wire_nodes, _) in q_wires.iter() {
+        for window in wire_nodes.0.windows(2) {
+            union_nodes(&mut parents, window[0].clone(), window[1].clone());
+        }
+    }
+
+    let mut net_label_pins: HashMap<&String, WireNode> = HashMap::new();
+    for (_, _, pin_model_collection, _, net_label) in q_devices.iter() {
+        let Some(net_label) = net_label else {
+            continue;
+        };
+
+        for pin_model in pin_model_collection.iter() {
+            let pin_node = WireNode::Pin(pin_model.uuid);
+            match net_label_pins.get(&net_label.name) {
+                Some(other_pin_node) => {
+                    union_nodes(&mut parents, other_pin_node.clone(), pin_node);
+                }
+                None => {
+                    net_label_pins.insert(&net_label.name, pin_node);
+                }
+            }
+        }
+    }
+
+    let mut drivers: HashMap<WireNode, Vec<(Entity, Vec2, String)>> = HashMap::new();
+    for (device_entity, device_position, pin_model_collection, generic_chip, net_label) in
+        q_devices.iter()
+    {
+        for pin_model in pin_model_collection.iter_outputs() {
+            let net = find_root(&mut parents, WireNode::Pin(pin_model.uuid));
+            drivers.entry(net).or_default().push((
+                device_entity,
+                device_position.0,
+                describe_pin(&pin_model.label, generic_chip, net_label),
+            ));
+        }
+    }
+
+    // inputs without a driver
+    for (device_entity, device_position, pin_model_collection, generic_chip, net_label) in
+        q_devices.iter()
+    {
+        // a net label on its own is neither an input nor an output
+        if net_label.is_some() {
+            continue;
+        }
+
+        for pin_model in pin_model_collection
+            .iter()
+            .filter(|pin_model| pin_model.pin_type == PinType::Input)
+        {
+            let net = find_root(&mut parents, WireNode::Pin(pin_model.uuid));
+
+            if !drivers.contains_key(&net) {
+                issues.push(DesignIssue {
+                    message: format!(
+                        "{} is not driven and reads Low.",
+                        describe_pin(&pin_model.label, generic_chip, net_label)
+                    ),
+                    entity: device_entity,
+                    position: device_position.0,
+                });
+            }
+        }
+    }
+
+    // nets with more than one driver, pull resistors and open-drain outputs are meant to share a net
+    for net_drivers in drivers.values() {
+        let net_drivers: Vec<&(Entity, Vec2, String)> = net_drivers
+            .iter()
+            .filter(|(entity, _, _)| !q_output_drives.contains(*entity))
+            .collect();
+        if net_drivers.len() <= 1 {
+            continue;
+        }
+
+        let (entity, position, _) = net_drivers[0].clone();
+        let names: Vec<&str> = net_drivers
+            .iter()
+            .map(|(_, _, name)| name.as_str())
+            .collect();
+
+        issues.push(DesignIssue {
+            message: format!("Net is driven by multiple outputs: {}.", names.join(", ")),
+            entity,
+            position,
+        });
+    }
+
+    // overlapping devices, annotations like frames and notes are meant to be placed over other devices
+    let is_annotation = |device_entity: Entity| {
+        q_device_refs
+            .get(device_entity)
+            .ok()
+            .and_then(|device| device_serializers.device_id(&device))
+            .and_then(|device_id| device_ids.categories.get(&device_id))
+            .is_some_and(|category| *category == DeviceCategory::Annotations)
+    };
+
+    let device_bboxes: Vec<(Entity, &BoundingBox)> = q_device_views
+        .iter()
+        .map(|(view, bbox)| (view.viewable().entity(), bbox))
+        .filter(|(device_entity, _)| !is_annotation(*device_entity))
+        .collect();
+
+    for (i, (device_entity, bbox)) in device_bboxes.iter().enumerate() {
+        for (other_entity, other_bbox) in device_bboxes.iter().skip(i + 1) {
+            if !bbox.intersects(other_bbox) {
+                continue;
+            }
+
+            let (Ok(device), Ok(other_device)) =
+                (q_devices.get(*device_entity), q_devices.get(*other_entity))
+            else {
+                continue;
+            };
+
+            issues.push(DesignIssue {
+                message: format!(
+                    "{} overlaps {}.",
+                    describe_device(device.3, device.4),
+                    describe_device(other_device.3, other_device.4)
+                ),
+                entity: *device_entity,
+                position: device.1 .0,
+            });
+        }
+    }
+
+    report.issues = issues;
+    report.open = true;
+}
+
+/// Selects the focused entity and centers the camera on it.
+pub fn focus_entity(
+    mut focus_events: EventReader<FocusEntityEvent>,
+    q_selected: Query<Entity, With<Selected>>,
+    mut q_camera: Query<&mut Transform, With<Camera2d>>,
+    mut commands: Commands,
+) {
+    for focus_ev in focus_events.read() {
+        for selected_entity in q_selected.iter() {
+            commands.entity(selected_entity).remove::<Selected>();
+        }
+
+        if let Some(mut entity_commands) = commands.get_entity(focus_ev.entity) {
+            entity_commands.insert(Selected);
+        }
+
+        if let Ok(mut camera_transform) = q_camera.get_single_mut() {
+            camera_transform.translation = focus_ev.position.extend(camera_transform.translation.z);
+        }
+    }
+}
+
+fn find_root(parents: &mut HashMap<WireNode, WireNode>, node: WireNode) -> WireNode {
+    let mut root = node.clone();
+    while let Some(parent) = parents.get(&root) {
+        if *parent == root {
+            break;
+        }
+        root = parent.clone();
+    }
+
+    // path compression
+    parents.insert(node, root.clone());
+    root
+}
+
+fn union_nodes(parents: &mut HashMap<WireNode, WireNode>, a: WireNode, b: WireNode) {
+    let root_a = find_root(parents, a);
+    let root_b = find_root(parents, b);
+
+    if root_a != root_b {
+        parents.insert(root_a, root_b);
+    }
+}
+
+fn describe_wire_node(wire_node: &WireNode) -> String {
+    match wire_node {
+        WireNode::Pin(uuid) => format!("pin {}", uuid),
+        WireNode::Joint(uuid) => format!("wire joint {}", uuid),
+    }
+}
+
+fn describe_device(generic_chip: Option<&GenericChip>, net_label: Option<&NetLabel>) -> String {
+    match (generic_chip, net_label) {
+        (Some(generic_chip), _) => generic_chip.name.clone(),
+        (_, Some(net_label)) => format!("Net label {}", net_label.name),
+        _ => "Device".into(),
+    }
+}
+
+fn describe_pin(
+    pin_label: &str,
+    generic_chip: Option<&GenericChip>,
+    net_label: Option<&NetLabel>,
+) -> String {
+    format!(
+        "Pin {} of {}",
+        pin_label,
+        describe_device(generic_chip, net_label)
+    )
+}
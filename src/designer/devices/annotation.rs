@@ -0,0 +1,380 @@
+//! Annotations document a board without taking part in the simulation.
+//! They are devices without pins, so selecting, moving, copying and saving works like for any other device.
+
+use bevy::{prelude::*, text::TextBounds};
+use bevy_prototype_lyon::prelude::*;
+use moonshine_core::object::{Object, ObjectInstance};
+use moonshine_view::{BuildView, ViewCommands};
+
+use crate::{
+    assets::common_assets::CommonAssets,
+    designer::{
+        bounding_box::BoundingBox,
+        pin::PinModelCollection,
+        position::Position,
+        render_settings::CircuitBoardRenderingSettings,
+        save_management::format::{DeviceProperties, PropertyValue},
+    },
+};
+
+use super::device::{Device, DeviceCategory, DeviceModelBundle, DeviceViewBundle, DeviceViewKind};
+
+/// Smallest size a frame can be resized to.
+const MIN_FRAME_SIZE: Vec2 = Vec2::new(60.0, 40.0);
+
+/// Free text on the board.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct TextAnnotation {
+    pub text: String,
+}
+
+impl Device for TextAnnotation {
+    fn create_bundle(position: Position) -> impl Bundle {
+        AnnotationBundle::new(
+            TextAnnotation {
+                text: "Text".into(),
+            },
+            position,
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "TEXT"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Annotations
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(text_annotation) = device.get::<TextAnnotation>() {
+            properties.set("text", PropertyValue::Text(text_annotation.text.clone()));
+        }
+        properties
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        if let Some(text) = properties.get_text("text") {
+            device.insert(TextAnnotation { text: text.into() });
+        }
+    }
+}
+
+/// Text on a colored note of a fixed size, the text wraps at the edge of the note.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct StickyNote {
+    pub text: String,
+}
+
+impl Device for StickyNote {
+    fn create_bundle(position: Position) -> impl Bundle {
+        AnnotationBundle::new(
+            StickyNote {
+                text: "Note".into(),
+            },
+            position,
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "NOTE"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Annotations
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(sticky_note) = device.get::<StickyNote>() {
+            properties.set("text", PropertyValue::Text(sticky_note.text.clone()));
+        }
+        properties
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        if let Some(text) = properties.get_text("text") {
+            device.insert(StickyNote { text: text.into() });
+        }
+    }
+}
+
+/// A named rectangle that visually groups a region of the board.
+/// Only the title bar can be grabbed, so the devices inside the frame stay selectable.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct NamedFrame {
+    pub name: String,
+    pub size: Vec2,
+}
+
+impl Device for NamedFrame {
+    fn create_bundle(position: Position) -> impl Bundle {
+        AnnotationBundle::new(
+            NamedFrame {
+                name: "Frame".into(),
+                size: Vec2::new(300.0, 200.0),
+            },
+            position,
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "FRAME"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Annotations
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(frame) = device.get::<NamedFrame>() {
+            properties.set("name", PropertyValue::Text(frame.name.clone()));
+            properties.set("width", PropertyValue::Float(frame.size.x as f64));
+            properties.set("height", PropertyValue::Float(frame.size.y as f64));
+        }
+        properties
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        let Some(mut frame) = device.get::<NamedFrame>().cloned() else {
+            return;
+        };
+
+        if let Some(name) = properties.get_text("name") {
+            frame.name = name.into();
+        }
+        if let Some(width) = properties.get_float("width") {
+            frame.size.x = (width as f32).max(MIN_FRAME_SIZE.x);
+        }
+        if let Some(height) = properties.get_float("height") {
+            frame.size.y = (height as f32).max(MIN_FRAME_SIZE.y);
+        }
+
+        device.insert(frame);
+    }
+}
+
+#[derive(Bundle, Clone)]
+pub struct AnnotationBundle<T: Component + Clone> {
+    annotation: T,
+    device_model_bundle: DeviceModelBundle,
+    // annotations have no pins, but devices are expected to have a collection
+    pin_model_collection: PinModelCollection,
+}
+
+impl<T: Component + Clone> AnnotationBundle<T> {
+    fn new(annotation: T, position: Position) -> Self {
+        Self {
+            annotation,
+            device_model_bundle: DeviceModelBundle::new(position),
+            pin_model_collection: PinModelCollection(Vec::new()),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct AnnotationText;
+
+#[derive(Bundle)]
+pub struct AnnotationTextBundle {
+    annotation_text: AnnotationText,
+    text_2d: Text2d,
+    text_color: TextColor,
+    text_font: TextFont,
+    text_layout: TextLayout,
+    transform: Transform,
+}
+
+impl AnnotationTextBundle {
+    fn new(
+        text: String,
+        font_size: f32,
+        common_assets: &CommonAssets,
+        justify: JustifyText,
+        translation: Vec3,
+    ) -> Self {
+        Self {
+            annotation_text: AnnotationText,
+            text_2d: Text2d::new(text),
+            text_color: TextColor(Color::BLACK),
+            text_font: TextFont {
+                font: common_assets.font.clone(),
+                font_size,
+                ..default()
+            },
+            text_layout: TextLayout::new_with_justify(justify),
+            transform: Transform::from_translation(translation),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct AnnotationBody;
+
+#[derive(Bundle)]
+pub struct AnnotationBodyBundle {
+    annotation_body: AnnotationBody,
+    fill: Fill,
+    stroke: Stroke,
+    shape_bundle: ShapeBundle,
+}
+
+impl AnnotationBodyBundle {
+    fn new(extents: Vec2, fill: Fill, stroke: Stroke, translation: Vec3) -> Self {
+        Self {
+            annotation_body: AnnotationBody,
+            fill,
+            stroke,
+            shape_bundle: ShapeBundle {
+                path: GeometryBuilder::build_as(&shapes::Rectangle {
+                    extents,
+                    ..default()
+                }),
+                transform: Transform::from_translation(translation),
+                ..default()
+            },
+        }
+    }
+}
+
+/// Rough size of a text, used to make free text selectable.
+fn estimate_text_extents(text: &str, font_size: f32) -> Vec2 {
+    let lines = text.lines().count().max(1);
+    let longest_line = text
+        .lines()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    Vec2::new(
+        (longest_line.max(1) as f32) * font_size * 0.6,
+        lines as f32 * font_size * 1.2,
+    )
+}
+
+impl BuildView<DeviceViewKind> for TextAnnotation {
+    fn build(
+        world: &World,
+        object: Object<DeviceViewKind>,
+        mut view: ViewCommands<DeviceViewKind>,
+    ) {
+        let common_assets = world.resource::<CommonAssets>();
+        let render_settings = world.resource::<CircuitBoardRenderingSettings>();
+
+        let position = world.get::<Position>(object.entity()).unwrap();
+        let text_annotation = world.get::<TextAnnotation>(object.entity()).unwrap();
+
+        let extents =
+            estimate_text_extents(&text_annotation.text, render_settings.annotation_font_size);
+
+        view.insert(DeviceViewBundle::new(position.clone(), extents))
+            .with_children(|device| {
+                device.spawn(AnnotationTextBundle::new(
+                    text_annotation.text.clone(),
+                    render_settings.annotation_font_size,
+                    common_assets,
+                    JustifyText::Center,
+                    Vec3::new(0.0, 0.0, 0.01),
+                ));
+            });
+    }
+}
+
+impl BuildView<DeviceViewKind> for StickyNote {
+    fn build(
+        world: &World,
+        object: Object<DeviceViewKind>,
+        mut view: ViewCommands<DeviceViewKind>,
+    ) {
+        let common_assets = world.resource::<CommonAssets>();
+        let render_settings = world.resource::<CircuitBoardRenderingSettings>();
+
+        let position = world.get::<Position>(object.entity()).unwrap();
+        let sticky_note = world.get::<StickyNote>(object.entity()).unwrap();
+
+        let extents = render_settings.sticky_note_extents;
+        let padding = 6.0;
+
+        view.insert(DeviceViewBundle::new(position.clone(), extents))
+            .with_children(|device| {
+                device.spawn(AnnotationBodyBundle::new(
+                    extents,
+                    Fill::color(render_settings.sticky_note_color),
+                    Stroke::new(
+                        render_settings.device_stroke_color,
+                        render_settings.device_stroke_width,
+                    ),
+                    Vec3::ZERO,
+                ));
+                device.spawn((
+                    AnnotationTextBundle::new(
+                        sticky_note.text.clone(),
+                        render_settings.annotation_font_size,
+                        common_assets,
+                        JustifyText::Left,
+                        Vec3::new(0.0, 0.0, 0.01),
+                    ),
+                    TextBounds::new(extents.x - 2.0 * padding, extents.y - 2.0 * padding),
+                ));
+            });
+    }
+}
+
+impl BuildView<DeviceViewKind> for NamedFrame {
+    fn build(
+        world: &World,
+        object: Object<DeviceViewKind>,
+        mut view: ViewCommands<DeviceViewKind>,
+    ) {
+        let common_assets = world.resource::<CommonAssets>();
+        let render_settings = world.resource::<CircuitBoardRenderingSettings>();
+
+        let position = world.get::<Position>(object.entity()).unwrap();
+        let frame = world.get::<NamedFrame>(object.entity()).unwrap();
+
+        let title_height = render_settings.frame_title_height;
+        let title_offset = Vec2::new(0.0, (frame.size.y - title_height) / 2.0);
+
+        view.insert(DeviceViewBundle::with_bounding_box(
+            position.clone(),
+            BoundingBox::rect_with_offset(
+                Vec2::new(frame.size.x, title_height) / 2.0,
+                title_offset,
+                true,
+            ),
+        ))
+        .with_children(|device| {
+            // behind all devices that are placed inside of the frame
+            device.spawn(AnnotationBodyBundle::new(
+                frame.size,
+                Fill::color(render_settings.frame_color),
+                Stroke::new(
+                    render_settings.frame_stroke_color,
+                    render_settings.device_stroke_width,
+                ),
+                Vec3::new(0.0, 0.0, -1.0),
+            ));
+            device.spawn(AnnotationBodyBundle::new(
+                Vec2::new(frame.size.x, title_height),
+                Fill::color(render_settings.frame_stroke_color),
+                Stroke::new(
+                    render_settings.frame_stroke_color,
+                    render_settings.device_stroke_width,
+                ),
+                title_offset.extend(-0.99),
+            ));
+            device.spawn(AnnotationTextBundle::new(
+                frame.name.clone(),
+                render_settings.annotation_font_size,
+                common_assets,
+                JustifyText::Center,
+                title_offset.extend(-0.98),
+            ));
+        });
+    }
+}
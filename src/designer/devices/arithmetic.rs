@@ -0,0 +1,258 @@
+//! Devices that calculate with unsigned multi-bit values.
+
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    position::Position,
+    save_management::format::DeviceProperties,
+};
+
+use super::{
+    bit_width::{numbered_pins, BitWidth},
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
+
+/// Amount of bits needed to shift by any amount below the bit width.
+pub fn shift_amount_width(bit_width: BitWidth) -> u32 {
+    (u32::BITS - (bit_width.0 - 1).leading_zeros()).max(1)
+}
+
+/// Adds `A*`, `B*` and the carry input `CI`, outputs the sum on `S*` and the carry on `CO`.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Adder;
+
+impl Adder {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = vec![PinModel::new_input("CI".into())];
+        pin_models.extend(numbered_pins("A", bit_width.0, PinModel::new_input));
+        pin_models.extend(numbered_pins("B", bit_width.0, PinModel::new_input));
+        pin_models.push(PinModel::new_output("CO".into()));
+        pin_models.extend(numbered_pins("S", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Adder {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            Adder,
+            BitWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "ADDER"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Arithmetic
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        BitWidth::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        BitWidth::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Outputs `A* + B*` on `S*` while `SUB` is low and `A* - B*` while it is high.
+/// `CO` is the carry when adding and the borrow when subtracting, so it is high if `B*` is greater than `A*`.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct AdderSubtractor;
+
+impl AdderSubtractor {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = vec![PinModel::new_input("SUB".into())];
+        pin_models.extend(numbered_pins("A", bit_width.0, PinModel::new_input));
+        pin_models.extend(numbered_pins("B", bit_width.0, PinModel::new_input));
+        pin_models.push(PinModel::new_output("CO".into()));
+        pin_models.extend(numbered_pins("S", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for AdderSubtractor {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            AdderSubtractor,
+            BitWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "ADD-SUB"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Arithmetic
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        BitWidth::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        BitWidth::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Compares the unsigned values `A*` and `B*`, exactly one of `LT`, `EQ` and `GT` is high.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Comparator;
+
+impl Comparator {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = numbered_pins("A", bit_width.0, PinModel::new_input);
+        pin_models.extend(numbered_pins("B", bit_width.0, PinModel::new_input));
+        pin_models.push(PinModel::new_output("LT".into()));
+        pin_models.push(PinModel::new_output("EQ".into()));
+        pin_models.push(PinModel::new_output("GT".into()));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Comparator {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            Comparator,
+            BitWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "COMPARATOR"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Arithmetic
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        BitWidth::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        BitWidth::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Multiplies the unsigned values `A*` and `B*`, the product `P*` has twice the bit width so it never overflows.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Multiplier;
+
+impl Multiplier {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = numbered_pins("A", bit_width.0, PinModel::new_input);
+        pin_models.extend(numbered_pins("B", bit_width.0, PinModel::new_input));
+        pin_models.extend(numbered_pins("P", bit_width.0 * 2, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Multiplier {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            Multiplier,
+            BitWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "MULTIPLIER"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Arithmetic
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        BitWidth::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        BitWidth::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Shifts `D*` by `N*` bits to the left while `DIR` is low and to the right while it is high.
+/// Bits shifted out are lost and zeros are shifted in.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct BarrelShifter;
+
+impl BarrelShifter {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = vec![PinModel::new_input("DIR".into())];
+        pin_models.extend(numbered_pins(
+            "N",
+            shift_amount_width(bit_width),
+            PinModel::new_input,
+        ));
+        pin_models.extend(numbered_pins("D", bit_width.0, PinModel::new_input));
+        pin_models.extend(numbered_pins("Y", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for BarrelShifter {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            BarrelShifter,
+            BitWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "SHIFTER"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Arithmetic
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        BitWidth::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        BitWidth::load_properties(device, properties, Self::create_pins);
+    }
+}
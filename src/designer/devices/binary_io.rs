@@ -12,12 +12,16 @@ use crate::{
         pin::{PinModel, PinModelCollection, PinViewBundle, PinViewCollectionBundle},
         position::Position,
         render_settings::CircuitBoardRenderingSettings,
+        save_management::format::DeviceProperties,
         signal::Signal,
     },
     find_descendant, get_cursor, get_model_mut,
 };
 
-use super::device::{Device, DeviceModelBundle, DeviceViewBundle, DeviceViewKind};
+use super::{
+    device::{Device, DeviceCategory, DeviceModelBundle, DeviceViewBundle, DeviceViewKind},
+    key_binding::KeyBinding,
+};
 
 #[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
@@ -31,11 +35,24 @@ impl Device for BinarySwitch {
     fn device_id() -> &'static str {
         "SWITCH"
     }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        KeyBinding::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        KeyBinding::load_properties(device, properties);
+    }
 }
 
 #[derive(Bundle, Clone)]
 pub struct BinarySwitchBundle {
     binary_switch: BinarySwitch,
+    key_binding: KeyBinding,
     device_model_bundle: DeviceModelBundle,
     pin_model_collection: PinModelCollection,
 }
@@ -44,6 +61,7 @@ impl BinarySwitchBundle {
     fn new(position: Position) -> Self {
         Self {
             binary_switch: BinarySwitch,
+            key_binding: KeyBinding::default(),
             device_model_bundle: DeviceModelBundle::new(position),
             pin_model_collection: PinModelCollection(vec![PinModel::new_output("Q".into())]),
         }
@@ -158,6 +176,94 @@ impl BinarySwitchPinCollectionBundle {
     }
 }
 
+/// An input that is only high while it is held down.
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component)]
+pub struct PushButton {
+    pub held_by_cursor: bool,
+}
+
+impl Device for PushButton {
+    fn create_bundle(position: Position) -> impl Bundle {
+        PushButtonBundle::new(position)
+    }
+
+    fn device_id() -> &'static str {
+        "BUTTON"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        KeyBinding::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        KeyBinding::load_properties(device, properties);
+    }
+}
+
+#[derive(Bundle, Clone)]
+pub struct PushButtonBundle {
+    push_button: PushButton,
+    key_binding: KeyBinding,
+    device_model_bundle: DeviceModelBundle,
+    pin_model_collection: PinModelCollection,
+}
+
+impl PushButtonBundle {
+    fn new(position: Position) -> Self {
+        Self {
+            push_button: PushButton::default(),
+            key_binding: KeyBinding::default(),
+            device_model_bundle: DeviceModelBundle::new(position),
+            pin_model_collection: PinModelCollection(vec![PinModel::new_output("Q".into())]),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct PushButtonKnob;
+
+#[derive(Bundle)]
+pub struct PushButtonKnobBundle {
+    push_button_knob: PushButtonKnob,
+    fill: Fill,
+    stroke: Stroke,
+    shape_bundle: ShapeBundle,
+    bounding_box: BoundingBox,
+}
+
+impl PushButtonKnobBundle {
+    fn new(render_settings: &CircuitBoardRenderingSettings) -> Self {
+        let radius = render_settings.binary_switch_extents.y * 0.35;
+
+        Self {
+            push_button_knob: PushButtonKnob,
+            fill: Fill::color(render_settings.push_button_color),
+            stroke: Stroke::new(
+                render_settings.device_stroke_color,
+                render_settings.device_stroke_width,
+            ),
+            shape_bundle: ShapeBundle {
+                path: GeometryBuilder::build_as(&shapes::Circle {
+                    radius,
+                    ..default()
+                }),
+                transform: Transform::from_xyz(
+                    -render_settings.binary_switch_extents.x / 4.0,
+                    0.0,
+                    0.01,
+                ),
+                ..default()
+            },
+            bounding_box: BoundingBox::circle_new(radius, false),
+        }
+    }
+}
+
 #[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
 pub struct BinaryDisplay;
@@ -170,6 +276,10 @@ impl Device for BinaryDisplay {
     fn device_id() -> &'static str {
         "DISPLAY"
     }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
 }
 
 #[derive(Bundle, Clone)]
@@ -342,6 +452,43 @@ impl BuildView<DeviceViewKind> for BinarySwitch {
     }
 }
 
+impl BuildView<DeviceViewKind> for PushButton {
+    fn build(
+        world: &World,
+        object: Object<DeviceViewKind>,
+        mut view: ViewCommands<DeviceViewKind>,
+    ) {
+        let common_assets = world.resource::<CommonAssets>();
+        let render_settings = world.resource::<CircuitBoardRenderingSettings>();
+
+        let position = world.get::<Position>(object.entity()).unwrap();
+        let pin_model_collection = world.get::<PinModelCollection>(object.entity()).unwrap();
+
+        view.insert(DeviceViewBundle::new(
+            position.clone(),
+            render_settings.binary_switch_extents,
+        ))
+        .with_children(|device| {
+            device.spawn(PushButtonKnobBundle::new(render_settings));
+            device.spawn(BinarySwitchBodyBundle::new(render_settings));
+            device.spawn(BoardBinaryDisplayBundle::new(
+                render_settings,
+                common_assets,
+                true,
+            ));
+
+            device
+                .spawn(BinarySwitchPinCollectionBundle::new())
+                .with_children(|pc| {
+                    pc.spawn(BinarySwitchPinBundle::new(
+                        render_settings,
+                        pin_model_collection["Q"].uuid,
+                    ));
+                });
+        });
+    }
+}
+
 impl BuildView<DeviceViewKind> for BinaryDisplay {
     fn build(
         world: &World,
@@ -382,7 +529,7 @@ pub fn update_board_binary_displays(
     q_board_binary_io: Query<
         (&PinModelCollection, &Viewable<DeviceViewKind>),
         (
-            Or<(With<BinarySwitch>, With<BinaryDisplay>)>,
+            Or<(With<BinarySwitch>, With<BinaryDisplay>, With<PushButton>)>,
             Changed<PinModelCollection>,
         ),
     >,
@@ -441,3 +588,41 @@ pub fn toggle_binary_switch(
         }
     }
 }
+
+/// Holds push-buttons while the left mouse button is held on them.
+pub fn press_push_buttons(
+    input: Res<ButtonInput<MouseButton>>,
+    q_knobs: Query<(Entity, &BoundingBox), With<PushButtonKnob>>,
+    q_cursor: Query<&Transform, With<Cursor>>,
+    q_parents: Query<&Parent>,
+    q_board_entities: Query<&View<DeviceViewKind>>,
+    mut q_push_buttons: Query<&mut PushButton>,
+) {
+    if input.just_released(MouseButton::Left) {
+        for mut push_button in q_push_buttons.iter_mut() {
+            if push_button.held_by_cursor {
+                push_button.held_by_cursor = false;
+            }
+        }
+    }
+
+    if !input.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let cursor_position = get_cursor!(q_cursor).translation.truncate();
+
+    for (knob_entity, bbox) in q_knobs.iter() {
+        if !bbox.point_in_bbox(cursor_position) {
+            continue;
+        }
+
+        if let Some(mut push_button) =
+            get_model_mut!(q_parents, q_board_entities, q_push_buttons, knob_entity)
+        {
+            push_button.held_by_cursor = true;
+        }
+
+        break;
+    }
+}
@@ -0,0 +1,63 @@
+//! Devices that work on multi-bit values.
+//! Values are spread over one pin per bit, e.g. `A0`, `A1`, `A2`, where `A0` is the least significant bit.
+
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    save_management::format::{DeviceProperties, PropertyValue},
+};
+
+/// Limits the amount of pins, wider values can be built by chaining devices.
+const MAX_BIT_WIDTH: u32 = 8;
+
+/// Amount of bits of the values a device works on.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct BitWidth(pub u32);
+
+impl Default for BitWidth {
+    fn default() -> Self {
+        Self(4)
+    }
+}
+
+impl BitWidth {
+    /// Mask with the lowest [`BitWidth`] bits set.
+    pub fn mask(&self) -> u64 {
+        (1 << self.0) - 1
+    }
+
+    pub fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(bit_width) = device.get::<BitWidth>() {
+            properties.set("bit_width", PropertyValue::Int(bit_width.0 as i64));
+        }
+        properties
+    }
+
+    /// Reads the bit width, limited to the supported range.
+    pub fn from_properties(properties: &DeviceProperties) -> Option<Self> {
+        properties
+            .get_int("bit_width")
+            .map(|bit_width| BitWidth(bit_width.clamp(1, MAX_BIT_WIDTH as i64) as u32))
+    }
+
+    /// Inserts the bit width and rebuilds the pins to match it.
+    pub fn load_properties(
+        device: &mut EntityWorldMut,
+        properties: &DeviceProperties,
+        create_pins: fn(BitWidth) -> PinModelCollection,
+    ) {
+        if let Some(bit_width) = BitWidth::from_properties(properties) {
+            device.insert((create_pins(bit_width), bit_width));
+        }
+    }
+}
+
+/// Creates the pins `{prefix}0` to `{prefix}{count - 1}`.
+pub fn numbered_pins(prefix: &str, count: u32, create: fn(String) -> PinModel) -> Vec<PinModel> {
+    (0..count)
+        .map(|i| create(format!("{}{}", prefix, i)))
+        .collect()
+}
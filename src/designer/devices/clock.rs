@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_prototype_lyon::{
     draw::{Fill, Stroke},
@@ -15,10 +17,14 @@ use crate::{
         pin::{PinModel, PinModelCollection, PinViewBundle, PinViewCollectionBundle},
         position::Position,
         render_settings::CircuitBoardRenderingSettings,
+        save_management::format::{DeviceProperties, PropertyValue},
     },
 };
 
-use super::device::{Device, DeviceModelBundle, DeviceViewBundle, DeviceViewKind};
+use super::device::{Device, DeviceCategory, DeviceModelBundle, DeviceViewBundle, DeviceViewKind};
+
+/// Shortest period that can be set, so the clock can not stall the simulation.
+const MIN_PERIOD: f32 = 0.01;
 
 #[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
@@ -34,6 +40,24 @@ impl Device for Clock {
     fn device_id() -> &'static str {
         "CLOCK"
     }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(clock) = device.get::<Clock>() {
+            properties.set("period", PropertyValue::Float(clock.period() as f64));
+        }
+        properties
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        if let Some(period) = properties.get_float("period") {
+            device.insert(Clock::new((period as f32).max(MIN_PERIOD)));
+        }
+    }
 }
 
 impl Clock {
@@ -42,6 +66,20 @@ impl Clock {
             timer: Timer::from_seconds(seconds, TimerMode::Repeating),
         }
     }
+
+    /// Seconds between two signal changes.
+    pub fn period(&self) -> f32 {
+        self.timer.duration().as_secs_f32()
+    }
+
+    /// Time since the last signal change.
+    pub fn elapsed(&self) -> Duration {
+        self.timer.elapsed()
+    }
+
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        self.timer.set_elapsed(elapsed);
+    }
 }
 
 #[derive(Bundle, Clone)]
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    position::Position,
+};
+
+use super::{
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
+
+/// Always drives its output high.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct ConstantHigh;
+
+impl Device for ConstantHigh {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            ConstantHigh,
+            GenericChipBundle::new(
+                position,
+                PinModelCollection(vec![PinModel::new_output("Q".into())]),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "HIGH"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+}
+
+/// Always drives its output low.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct ConstantLow;
+
+impl Device for ConstantLow {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            ConstantLow,
+            GenericChipBundle::new(
+                position,
+                PinModelCollection(vec![PinModel::new_output("Q".into())]),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "LOW"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+}
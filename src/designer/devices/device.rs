@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use bevy::prelude::*;
 use moonshine_core::kind::Kind;
 use moonshine_view::Viewable;
@@ -8,16 +10,29 @@ use crate::{
         cursor::{Cursor, CursorState},
         model::Model,
         position::Position,
+        save_management::format::DeviceProperties,
         selection::{Dragged, Selected},
     },
     events::SpawnDeviceEvent,
     get_cursor_mut,
 };
 
+use super::generic_chip::GenericChip;
+
 pub trait Device: 'static + Send + Sync + Component {
     //TODO: add eval method (tick, simulate)
     fn create_bundle(position: Position) -> impl Bundle;
     fn device_id() -> &'static str;
+    /// Group of the device in the chip selector.
+    fn category() -> DeviceCategory;
+
+    /// Device specific settings that are written to save files.
+    fn save_properties(_device: &EntityRef) -> DeviceProperties {
+        DeviceProperties::default()
+    }
+
+    /// Restores the settings written by [`Device::save_properties`].
+    fn load_properties(_device: &mut EntityWorldMut, _properties: &DeviceProperties) {}
 }
 
 pub trait RegisterDevice {
@@ -33,10 +48,27 @@ impl RegisterDevice for App {
         );
 
         // store device_id in resource
+        let mut device_ids = self
+            .world_mut()
+            .get_resource_or_insert_with::<DeviceIds>(DeviceIds::default);
+        device_ids.devices.push(T::device_id().into());
+        device_ids
+            .categories
+            .insert(T::device_id().into(), T::category());
+
+        // store functions to save and load the device
         self.world_mut()
-            .get_resource_or_insert_with::<DeviceIds>(DeviceIds::default)
-            .devices
-            .push(T::device_id().into());
+            .get_resource_or_insert_with::<DeviceSerializers>(DeviceSerializers::default)
+            .0
+            .insert(
+                T::device_id().into(),
+                DeviceSerializer {
+                    spawn: |world, position| world.spawn(T::create_bundle(position)).id(),
+                    is_device: |device| device.contains::<T>(),
+                    save_properties: T::save_properties,
+                    load_properties: T::load_properties,
+                },
+            );
 
         self
     }
@@ -74,6 +106,74 @@ fn spawn_device<T: Device>(
 #[derive(Resource, Default)]
 pub struct DeviceIds {
     pub devices: Vec<String>,
+    pub categories: HashMap<String, DeviceCategory>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DeviceCategory {
+    Gates,
+    FlipFlops,
+    Io,
+    Plexers,
+    Arithmetic,
+    Memory,
+    /// Text, notes and frames that only document the board.
+    Annotations,
+    /// Parts of imported component libraries.
+    Custom,
+}
+
+impl DeviceCategory {
+    pub const ALL: [DeviceCategory; 8] = [
+        DeviceCategory::Gates,
+        DeviceCategory::FlipFlops,
+        DeviceCategory::Io,
+        DeviceCategory::Plexers,
+        DeviceCategory::Arithmetic,
+        DeviceCategory::Memory,
+        DeviceCategory::Annotations,
+        DeviceCategory::Custom,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeviceCategory::Gates => "Gates",
+            DeviceCategory::FlipFlops => "Flip-Flops",
+            DeviceCategory::Io => "I/O",
+            DeviceCategory::Plexers => "Plexers",
+            DeviceCategory::Arithmetic => "Arithmetic",
+            DeviceCategory::Memory => "Memory",
+            DeviceCategory::Annotations => "Annotations",
+            DeviceCategory::Custom => "Custom",
+        }
+    }
+}
+
+/// Type erased [`Device`] functions used to write devices to save files and spawn them again.
+pub struct DeviceSerializer {
+    pub spawn: fn(&mut World, Position) -> Entity,
+    pub is_device: fn(&EntityRef) -> bool,
+    pub save_properties: fn(&EntityRef) -> DeviceProperties,
+    pub load_properties: fn(&mut EntityWorldMut, &DeviceProperties),
+}
+
+/// Maps every device id to its [`DeviceSerializer`].
+#[derive(Resource, Default)]
+pub struct DeviceSerializers(pub HashMap<String, DeviceSerializer>);
+
+impl DeviceSerializers {
+    /// Finds the device id of a device entity.
+    /// Chips are identified by their name, because copied chips dont keep their device marker.
+    pub fn device_id(&self, device: &EntityRef) -> Option<String> {
+        if let Some(generic_chip) = device.get::<GenericChip>() {
+            return Some(generic_chip.name.clone());
+        }
+
+        self.0
+            .iter()
+            .find(|(_, serializer)| (serializer.is_device)(device))
+            .map(|(device_id, _)| device_id.clone())
+    }
 }
 
 #[derive(Component)]
@@ -89,13 +189,17 @@ pub struct DeviceViewBundle {
 
 impl DeviceViewBundle {
     pub fn new(position: Position, extents: Vec2) -> Self {
+        Self::with_bounding_box(
+            position,
+            BoundingBox::rect_with_offset(extents / Vec2::new(2.0, 2.0), Vec2::ZERO, true),
+        )
+    }
+
+    /// For devices that can only be grabbed at a part of their body.
+    pub fn with_bounding_box(position: Position, bounding_box: BoundingBox) -> Self {
         Self {
             device_view: DeviceView,
-            bounding_box: BoundingBox::rect_with_offset(
-                extents / Vec2::new(2.0, 2.0),
-                Vec2::ZERO,
-                true,
-            ),
+            bounding_box,
             transform: Transform::from_xyz(position.0.x, position.0.y, 0.0),
             visibility: Visibility::default(),
         }
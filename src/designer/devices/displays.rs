@@ -0,0 +1,658 @@
+//! Output devices that show the signals of their inputs as lights.
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use moonshine_core::object::{Object, ObjectInstance};
+use moonshine_view::prelude::*;
+
+use crate::{
+    assets::common_assets::CommonAssets,
+    designer::{
+        pin::{
+            PinLabelBundle, PinModel, PinModelCollection, PinViewBundle, PinViewCollectionBundle,
+        },
+        position::Position,
+        render_settings::CircuitBoardRenderingSettings,
+        save_management::format::{DeviceProperties, PropertyValue},
+        signal::Signal,
+    },
+};
+
+use super::{
+    bit_width::numbered_pins,
+    device::{Device, DeviceCategory, DeviceModelBundle, DeviceViewBundle, DeviceViewKind},
+};
+
+/// Limits the amount of pins of LED matrices.
+const MAX_MATRIX_SIZE: i64 = 8;
+
+/// Inputs of the seven-segment display from bottom to top.
+const SEGMENT_INPUTS: [&str; 8] = ["DP", "G", "F", "E", "D", "C", "B", "A"];
+
+/// Lit segments of the hex digits 0 to F, bit 0 is segment A and bit 6 is segment G.
+const HEX_DIGIT_SEGMENTS: [u8; 16] = [
+    0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7D, 0x07, 0x7F, 0x6F, 0x77, 0x7C, 0x39, 0x5E, 0x79, 0x71,
+];
+
+/// A digit with one input per segment and one for the decimal point.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct SevenSegmentDisplay;
+
+impl Device for SevenSegmentDisplay {
+    fn create_bundle(position: Position) -> impl Bundle {
+        DisplayBundle::new(
+            SevenSegmentDisplay,
+            position,
+            SEGMENT_INPUTS
+                .iter()
+                .map(|label| PinModel::new_input(label.to_string()))
+                .collect(),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "7-SEGMENT"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+}
+
+/// A digit that shows the 4-bit value of `D*` in hex.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct HexDigitDisplay;
+
+impl Device for HexDigitDisplay {
+    fn create_bundle(position: Position) -> impl Bundle {
+        DisplayBundle::new(
+            HexDigitDisplay,
+            position,
+            numbered_pins("D", 4, PinModel::new_input),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "HEX-DIGIT"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+}
+
+/// A single light in a configurable color.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Led {
+    pub color: Color,
+}
+
+impl Device for Led {
+    fn create_bundle(position: Position) -> impl Bundle {
+        DisplayBundle::new(
+            Led {
+                color: Color::srgb(1.0, 0.1, 0.1),
+            },
+            position,
+            vec![PinModel::new_input("A".into())],
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "LED"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(led) = device.get::<Led>() {
+            properties.set("color", PropertyValue::Text(led.color.to_srgba().to_hex()));
+        }
+        properties
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        let Some(color) = properties.get_text("color") else {
+            return;
+        };
+
+        match Srgba::hex(color) {
+            Ok(color) => {
+                device.insert(Led {
+                    color: color.into(),
+                });
+            }
+            Err(error) => warn!("Ignoring invalid LED color \"{}\": {}", color, error),
+        }
+    }
+}
+
+/// A grid of lights, the light in row r and column c is lit while `R{r}` and `C{c}` are high.
+/// Driving the rows one after another shows any pattern, like real multiplexed LED matrices.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct LedMatrix {
+    pub rows: u32,
+    pub columns: u32,
+}
+
+impl LedMatrix {
+    fn create_pins(&self) -> Vec<PinModel> {
+        let mut pin_models = numbered_pins("C", self.columns, PinModel::new_input);
+        pin_models.extend(numbered_pins("R", self.rows, PinModel::new_input));
+        pin_models
+    }
+}
+
+impl Device for LedMatrix {
+    fn create_bundle(position: Position) -> impl Bundle {
+        let led_matrix = LedMatrix {
+            rows: 4,
+            columns: 4,
+        };
+        let pin_models = led_matrix.create_pins();
+
+        DisplayBundle::new(led_matrix, position, pin_models)
+    }
+
+    fn device_id() -> &'static str {
+        "LED-MATRIX"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(led_matrix) = device.get::<LedMatrix>() {
+            properties.set("rows", PropertyValue::Int(led_matrix.rows as i64));
+            properties.set("columns", PropertyValue::Int(led_matrix.columns as i64));
+        }
+        properties
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        let (Some(rows), Some(columns)) =
+            (properties.get_int("rows"), properties.get_int("columns"))
+        else {
+            return;
+        };
+
+        let led_matrix = LedMatrix {
+            rows: rows.clamp(1, MAX_MATRIX_SIZE) as u32,
+            columns: columns.clamp(1, MAX_MATRIX_SIZE) as u32,
+        };
+
+        device.insert((PinModelCollection(led_matrix.create_pins()), led_matrix));
+    }
+}
+
+#[derive(Bundle, Clone)]
+pub struct DisplayBundle<T: Component + Clone> {
+    display: T,
+    device_model_bundle: DeviceModelBundle,
+    pin_model_collection: PinModelCollection,
+}
+
+impl<T: Component + Clone> DisplayBundle<T> {
+    fn new(display: T, position: Position, pin_models: Vec<PinModel>) -> Self {
+        Self {
+            display,
+            device_model_bundle: DeviceModelBundle::new(position),
+            pin_model_collection: PinModelCollection(pin_models),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct DisplayBody;
+
+#[derive(Bundle)]
+pub struct DisplayBodyBundle {
+    display_body: DisplayBody,
+    fill: Fill,
+    stroke: Stroke,
+    shape_bundle: ShapeBundle,
+}
+
+impl DisplayBodyBundle {
+    fn new(render_settings: &CircuitBoardRenderingSettings, extents: Vec2) -> Self {
+        Self {
+            display_body: DisplayBody,
+            fill: Fill::color(render_settings.display_color),
+            stroke: Stroke::new(
+                render_settings.device_stroke_color,
+                render_settings.device_stroke_width,
+            ),
+            shape_bundle: ShapeBundle {
+                path: GeometryBuilder::build_as(&shapes::Rectangle {
+                    extents,
+                    radii: Some(BorderRadii::single(render_settings.device_border_radius)),
+                    ..default()
+                }),
+                ..default()
+            },
+        }
+    }
+}
+
+/// A segment of a seven-segment or hex digit display, 0 to 6 are the segments A to G and 7 is the decimal point.
+#[derive(Component)]
+pub struct DisplaySegment(pub u8);
+
+/// A light of an LED or LED matrix.
+#[derive(Component)]
+pub struct DisplayLight {
+    pub row: u32,
+    pub column: u32,
+}
+
+#[derive(Bundle)]
+pub struct DisplayLightBundle<T: Component> {
+    light: T,
+    fill: Fill,
+    shape_bundle: ShapeBundle,
+}
+
+impl<T: Component> DisplayLightBundle<T> {
+    fn new(light: T, path: Path, color: Color, translation: Vec2) -> Self {
+        Self {
+            light,
+            fill: Fill::color(color),
+            shape_bundle: ShapeBundle {
+                path,
+                transform: Transform::from_translation(translation.extend(0.01)),
+                ..default()
+            },
+        }
+    }
+}
+
+#[derive(Component)]
+struct DisplayPinCollection;
+
+#[derive(Bundle)]
+struct DisplayPinCollectionBundle {
+    display_pin_collection: DisplayPinCollection,
+    pin_collection_bundle: PinViewCollectionBundle,
+}
+
+impl DisplayPinCollectionBundle {
+    fn new() -> Self {
+        Self {
+            display_pin_collection: DisplayPinCollection,
+            pin_collection_bundle: PinViewCollectionBundle::new(),
+        }
+    }
+
+    /// Spawns the input pins on the left side from bottom to top, spaced like the pins of chips.
+    fn spawn_pins(
+        pin_collection: &mut ChildBuilder,
+        render_settings: &CircuitBoardRenderingSettings,
+        common_assets: &CommonAssets,
+        extents: Vec2,
+        pin_model_collection: &PinModelCollection,
+    ) {
+        let num_inputs = pin_model_collection.num_inputs();
+
+        for (i, pin_model) in pin_model_collection.iter_inputs().enumerate() {
+            let y = match num_inputs {
+                1 => 0.0,
+                _ => ((i as f32 + 0.75) * render_settings.chip_pin_gap) - (extents.y / 2.0),
+            };
+
+            pin_collection
+                .spawn(PinViewBundle::new(
+                    render_settings,
+                    pin_model.uuid,
+                    render_settings.chip_pin_radius,
+                    Vec3::new(-(extents.x / 2.0), y, 0.01),
+                ))
+                .with_children(|pc| {
+                    // single pins are obvious, their label would cover the light
+                    if num_inputs == 1 {
+                        return;
+                    }
+
+                    pc.spawn(PinLabelBundle::new(
+                        pin_model.label.clone(),
+                        TextColor(render_settings.display_label_color),
+                        TextFont {
+                            font: common_assets.font.clone(),
+                            font_size: render_settings.chip_pin_label_font_size,
+                            ..default()
+                        },
+                        Vec3::new(14.0, 0.0, 0.2),
+                    ));
+                });
+        }
+    }
+}
+
+/// Space on the left side for the pin labels.
+const PIN_LABEL_MARGIN: f32 = 30.0;
+
+/// Height that fits all inputs with the spacing of chip pins.
+fn pins_height(render_settings: &CircuitBoardRenderingSettings, num_inputs: usize) -> f32 {
+    render_settings.chip_pin_gap * (num_inputs as f32 + 0.5)
+}
+
+/// Extents and center of the segments A to G of a digit with the given size.
+fn segment_rects(size: Vec2) -> [(Vec2, Vec2); 7] {
+    let thickness = size.x * 0.15;
+    let horizontal = Vec2::new(size.x - thickness, thickness);
+    let vertical = Vec2::new(thickness, size.y / 2.0 - thickness);
+    let (x, y) = (size.x / 2.0 - thickness / 2.0, size.y / 4.0);
+
+    [
+        (horizontal, Vec2::new(0.0, size.y / 2.0 - thickness / 2.0)),
+        (vertical, Vec2::new(x, y)),
+        (vertical, Vec2::new(x, -y)),
+        (horizontal, Vec2::new(0.0, -size.y / 2.0 + thickness / 2.0)),
+        (vertical, Vec2::new(-x, -y)),
+        (vertical, Vec2::new(-x, y)),
+        (horizontal, Vec2::ZERO),
+    ]
+}
+
+/// Builds the view of a digit with pins on the left and the segments on the right.
+fn build_digit_view(
+    world: &World,
+    object: Object<DeviceViewKind>,
+    mut view: ViewCommands<DeviceViewKind>,
+    with_decimal_point: bool,
+) {
+    let common_assets = world.resource::<CommonAssets>();
+    let render_settings = world.resource::<CircuitBoardRenderingSettings>();
+
+    let position = world.get::<Position>(object.entity()).unwrap();
+    let pin_model_collection = world.get::<PinModelCollection>(object.entity()).unwrap();
+
+    let height = pins_height(render_settings, pin_model_collection.num_inputs());
+    let digit_size = Vec2::new(height * 0.4, height * 0.7);
+    let extents = Vec2::new(PIN_LABEL_MARGIN + digit_size.x * 1.6, height);
+    let digit_center = Vec2::new(PIN_LABEL_MARGIN / 2.0, 0.0);
+
+    view.insert(DeviceViewBundle::new(position.clone(), extents))
+        .with_children(|device| {
+            device.spawn(DisplayBodyBundle::new(render_settings, extents));
+
+            for (index, (segment_extents, center)) in
+                segment_rects(digit_size).into_iter().enumerate()
+            {
+                device.spawn(DisplayLightBundle::new(
+                    DisplaySegment(index as u8),
+                    GeometryBuilder::build_as(&shapes::Rectangle {
+                        extents: segment_extents * 0.9,
+                        ..default()
+                    }),
+                    render_settings.light_off_color,
+                    digit_center + center,
+                ));
+            }
+
+            if with_decimal_point {
+                let thickness = digit_size.x * 0.15;
+                device.spawn(DisplayLightBundle::new(
+                    DisplaySegment(7),
+                    GeometryBuilder::build_as(&shapes::Circle {
+                        radius: thickness * 0.6,
+                        ..default()
+                    }),
+                    render_settings.light_off_color,
+                    digit_center
+                        + Vec2::new(
+                            digit_size.x / 2.0 + thickness,
+                            -digit_size.y / 2.0 + thickness / 2.0,
+                        ),
+                ));
+            }
+
+            device
+                .spawn(DisplayPinCollectionBundle::new())
+                .with_children(|pc| {
+                    DisplayPinCollectionBundle::spawn_pins(
+                        pc,
+                        render_settings,
+                        common_assets,
+                        extents,
+                        pin_model_collection,
+                    );
+                });
+        });
+}
+
+impl BuildView<DeviceViewKind> for SevenSegmentDisplay {
+    fn build(world: &World, object: Object<DeviceViewKind>, view: ViewCommands<DeviceViewKind>) {
+        build_digit_view(world, object, view, true);
+    }
+}
+
+impl BuildView<DeviceViewKind> for HexDigitDisplay {
+    fn build(world: &World, object: Object<DeviceViewKind>, view: ViewCommands<DeviceViewKind>) {
+        build_digit_view(world, object, view, false);
+    }
+}
+
+impl BuildView<DeviceViewKind> for Led {
+    fn build(
+        world: &World,
+        object: Object<DeviceViewKind>,
+        mut view: ViewCommands<DeviceViewKind>,
+    ) {
+        let common_assets = world.resource::<CommonAssets>();
+        let render_settings = world.resource::<CircuitBoardRenderingSettings>();
+
+        let position = world.get::<Position>(object.entity()).unwrap();
+        let pin_model_collection = world.get::<PinModelCollection>(object.entity()).unwrap();
+
+        let extents = render_settings.binary_display_extents;
+
+        view.insert(DeviceViewBundle::new(position.clone(), extents))
+            .with_children(|device| {
+                device.spawn(DisplayBodyBundle::new(render_settings, extents));
+                device.spawn(DisplayLightBundle::new(
+                    DisplayLight { row: 0, column: 0 },
+                    GeometryBuilder::build_as(&shapes::Circle {
+                        radius: render_settings.led_radius,
+                        ..default()
+                    }),
+                    render_settings.light_off_color,
+                    Vec2::ZERO,
+                ));
+
+                device
+                    .spawn(DisplayPinCollectionBundle::new())
+                    .with_children(|pc| {
+                        DisplayPinCollectionBundle::spawn_pins(
+                            pc,
+                            render_settings,
+                            common_assets,
+                            extents,
+                            pin_model_collection,
+                        );
+                    });
+            });
+    }
+}
+
+impl BuildView<DeviceViewKind> for LedMatrix {
+    fn build(
+        world: &World,
+        object: Object<DeviceViewKind>,
+        mut view: ViewCommands<DeviceViewKind>,
+    ) {
+        let common_assets = world.resource::<CommonAssets>();
+        let render_settings = world.resource::<CircuitBoardRenderingSettings>();
+
+        let position = world.get::<Position>(object.entity()).unwrap();
+        let pin_model_collection = world.get::<PinModelCollection>(object.entity()).unwrap();
+        let led_matrix = world.get::<LedMatrix>(object.entity()).unwrap();
+
+        let spacing = render_settings.led_radius * 2.5;
+        let grid_size = Vec2::new(led_matrix.columns as f32, led_matrix.rows as f32) * spacing;
+        let extents = Vec2::new(
+            PIN_LABEL_MARGIN + grid_size.x + spacing,
+            pins_height(render_settings, pin_model_collection.num_inputs()).max(grid_size.y),
+        );
+        // row 0 is at the top, column 0 on the left
+        let first_light = Vec2::new(
+            PIN_LABEL_MARGIN / 2.0 - grid_size.x / 2.0 + spacing / 2.0,
+            grid_size.y / 2.0 - spacing / 2.0,
+        );
+
+        view.insert(DeviceViewBundle::new(position.clone(), extents))
+            .with_children(|device| {
+                device.spawn(DisplayBodyBundle::new(render_settings, extents));
+
+                for row in 0..led_matrix.rows {
+                    for column in 0..led_matrix.columns {
+                        device.spawn(DisplayLightBundle::new(
+                            DisplayLight { row, column },
+                            GeometryBuilder::build_as(&shapes::Circle {
+                                radius: render_settings.led_radius,
+                                ..default()
+                            }),
+                            render_settings.light_off_color,
+                            first_light + Vec2::new(column as f32, -(row as f32)) * spacing,
+                        ));
+                    }
+                }
+
+                device
+                    .spawn(DisplayPinCollectionBundle::new())
+                    .with_children(|pc| {
+                        DisplayPinCollectionBundle::spawn_pins(
+                            pc,
+                            render_settings,
+                            common_assets,
+                            extents,
+                            pin_model_collection,
+                        );
+                    });
+            });
+    }
+}
+
+/// Color of a light that is lit by the signal, lights with a conflict show the conflict color.
+fn light_color(
+    render_settings: &CircuitBoardRenderingSettings,
+    signal: &Signal,
+    on_color: Color,
+) -> Color {
+    match signal {
+        Signal::High => on_color,
+        Signal::Low => render_settings.light_off_color,
+        Signal::Conflict => render_settings.signal_conflict_color,
+    }
+}
+
+/// Signals of the segments A to G and the decimal point.
+fn segment_signals(pin_model_collection: &PinModelCollection, is_hex_digit: bool) -> [Signal; 8] {
+    if !is_hex_digit {
+        return ["A", "B", "C", "D", "E", "F", "G", "DP"].map(|label| {
+            pin_model_collection[label]
+                .signal_state
+                .get_signal()
+                .clone()
+        });
+    }
+
+    match pin_model_collection.read_bits("D", 4) {
+        Some(digit) => std::array::from_fn(|segment| {
+            Signal::from((HEX_DIGIT_SEGMENTS[digit as usize] >> segment) & 1 == 1)
+        }),
+        None => std::array::from_fn(|_| Signal::Conflict),
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_segment_displays(
+    q_displays: Query<
+        (
+            &PinModelCollection,
+            &Viewable<DeviceViewKind>,
+            Has<HexDigitDisplay>,
+        ),
+        (
+            Or<(With<SevenSegmentDisplay>, With<HexDigitDisplay>)>,
+            Changed<PinModelCollection>,
+        ),
+    >,
+    q_children: Query<&Children>,
+    mut q_segments: Query<(&DisplaySegment, &mut Fill)>,
+    render_settings: Res<CircuitBoardRenderingSettings>,
+) {
+    for (pin_model_collection, viewable, is_hex_digit) in q_displays.iter() {
+        let signals = segment_signals(pin_model_collection, is_hex_digit);
+
+        for child_entity in q_children.iter_descendants(viewable.view().entity()) {
+            if let Ok((segment, mut fill)) = q_segments.get_mut(child_entity) {
+                fill.color = light_color(
+                    &render_settings,
+                    &signals[segment.0 as usize],
+                    render_settings.segment_on_color,
+                );
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn update_leds(
+    q_leds: Query<
+        (
+            &PinModelCollection,
+            &Viewable<DeviceViewKind>,
+            Option<&Led>,
+            Option<&LedMatrix>,
+        ),
+        (
+            Or<(With<Led>, With<LedMatrix>)>,
+            Or<(Changed<PinModelCollection>, Changed<Led>)>,
+        ),
+    >,
+    q_children: Query<&Children>,
+    mut q_lights: Query<(&DisplayLight, &mut Fill)>,
+    render_settings: Res<CircuitBoardRenderingSettings>,
+) {
+    for (pin_model_collection, viewable, led, led_matrix) in q_leds.iter() {
+        for child_entity in q_children.iter_descendants(viewable.view().entity()) {
+            let Ok((light, mut fill)) = q_lights.get_mut(child_entity) else {
+                continue;
+            };
+
+            fill.color = match (led, led_matrix) {
+                (Some(led), _) => light_color(
+                    &render_settings,
+                    pin_model_collection["A"].signal_state.get_signal(),
+                    led.color,
+                ),
+                (_, Some(_)) => {
+                    let row = pin_model_collection[format!("R{}", light.row).as_str()]
+                        .signal_state
+                        .get_signal();
+                    let column = pin_model_collection[format!("C{}", light.column).as_str()]
+                        .signal_state
+                        .get_signal();
+
+                    let signal = match (row, column) {
+                        (Signal::Conflict, _) | (_, Signal::Conflict) => Signal::Conflict,
+                        (Signal::High, Signal::High) => Signal::High,
+                        _ => Signal::Low,
+                    };
+
+                    light_color(&render_settings, &signal, render_settings.segment_on_color)
+                }
+                (None, None) => continue,
+            };
+        }
+    }
+}
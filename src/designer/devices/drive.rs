@@ -0,0 +1,140 @@
+//! Outputs that do not always drive their net with full strength.
+//!
+//! Pull resistors only set the level of a net that no other output drives, and open-drain
+//! outputs only drive their net while they are Low. Together they allow several outputs to share
+//! one net, e.g. a wired-AND bus. A net that no output drives at all is Low.
+
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    position::Position,
+};
+
+use super::{
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
+
+/// How the outputs of a device drive their nets, devices without it always drive them.
+#[derive(Component, Reflect, Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Component)]
+pub enum OutputDrive {
+    /// Loses against every other output on the net, weak outputs that disagree cause a conflict.
+    Weak,
+    /// Drives Low, but leaves the net undriven instead of driving High.
+    OpenDrain,
+}
+
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct PullUp;
+
+impl Device for PullUp {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            PullUp,
+            OutputDrive::Weak,
+            GenericChipBundle::new(
+                position,
+                PinModelCollection(vec![PinModel::new_output("Q".into())]),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "PULL-UP"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+}
+
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct PullDown;
+
+impl Device for PullDown {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            PullDown,
+            OutputDrive::Weak,
+            GenericChipBundle::new(
+                position,
+                PinModelCollection(vec![PinModel::new_output("Q".into())]),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "PULL-DOWN"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+}
+
+/// Inverter with an open-drain output, evaluated like NOT.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct OpenDrainNot;
+
+impl Device for OpenDrainNot {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            OpenDrainNot,
+            OutputDrive::OpenDrain,
+            GenericChipBundle::new(
+                position,
+                PinModelCollection(vec![
+                    PinModel::new_input("A".into()),
+                    PinModel::new_output("Q".into()),
+                ]),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "NOT-OD"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Gates
+    }
+}
+
+/// NAND gate with an open-drain output, evaluated like NAND-2.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct OpenDrainNand2;
+
+impl Device for OpenDrainNand2 {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            OpenDrainNand2,
+            OutputDrive::OpenDrain,
+            GenericChipBundle::new(
+                position,
+                PinModelCollection(vec![
+                    PinModel::new_input("B".into()),
+                    PinModel::new_input("A".into()),
+                    PinModel::new_output("Q".into()),
+                ]),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "NAND-2-OD"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Gates
+    }
+}
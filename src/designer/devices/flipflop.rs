@@ -0,0 +1,83 @@
+//! Options that are shared by all clocked flip-flops.
+
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    save_management::format::{DeviceProperties, PropertyValue},
+    signal::{Signal, SignalState},
+};
+
+/// Label of the inverted output of flip-flops and latches.
+pub const INVERTED_OUTPUT: &str = "~Q";
+
+/// Creates the `~Q` output, which starts out high because `Q` starts out low.
+pub fn inverted_output() -> PinModel {
+    PinModel {
+        signal_state: SignalState::new(Signal::High),
+        ..PinModel::new_output(INVERTED_OUTPUT.into())
+    }
+}
+
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component)]
+pub struct FlipFlopOptions {
+    /// Triggers on the falling instead of the rising edge of `C`.
+    pub falling_edge: bool,
+    /// Adds the asynchronous, active high `PRE` and `CLR` inputs.
+    pub preset_clear: bool,
+    /// Adds the `EN` input, edges on `C` are ignored while it is low.
+    pub clock_enable: bool,
+}
+
+impl FlipFlopOptions {
+    /// Creates the pins of a flip-flop, `inputs` are ordered from bottom to top.
+    pub fn pin_model_collection(&self, inputs: &[&str]) -> PinModelCollection {
+        let mut pin_models = Vec::new();
+
+        if self.preset_clear {
+            pin_models.push(PinModel::new_input("CLR".into()));
+        }
+        pin_models.extend(
+            inputs
+                .iter()
+                .map(|label| PinModel::new_input(label.to_string())),
+        );
+        if self.clock_enable {
+            pin_models.push(PinModel::new_input("EN".into()));
+        }
+        if self.preset_clear {
+            pin_models.push(PinModel::new_input("PRE".into()));
+        }
+
+        pin_models.push(inverted_output());
+        pin_models.push(PinModel::new_output("Q".into()));
+
+        PinModelCollection(pin_models)
+    }
+
+    pub fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(options) = device.get::<FlipFlopOptions>() {
+            properties.set("falling_edge", PropertyValue::Bool(options.falling_edge));
+            properties.set("preset_clear", PropertyValue::Bool(options.preset_clear));
+            properties.set("clock_enable", PropertyValue::Bool(options.clock_enable));
+        }
+        properties
+    }
+
+    /// Inserts the options and rebuilds the pins to match them.
+    pub fn load_properties(
+        device: &mut EntityWorldMut,
+        properties: &DeviceProperties,
+        inputs: &[&str],
+    ) {
+        let options = FlipFlopOptions {
+            falling_edge: properties.get_bool("falling_edge").unwrap_or_default(),
+            preset_clear: properties.get_bool("preset_clear").unwrap_or_default(),
+            clock_enable: properties.get_bool("clock_enable").unwrap_or_default(),
+        };
+
+        device.insert((options.pin_model_collection(inputs), options));
+    }
+}
@@ -210,26 +210,33 @@ impl GenericChipPinCollectionBundle {
                 });
         }
 
-        // Output pins
-        let output_pin_model = pin_model_collection.iter_outputs().next().unwrap(); //TODO: only works with one output chips
-        pin_collection
-            .spawn(GenericChipOutputPinBundle::new(
-                render_settings,
-                output_pin_model.uuid,
-                Vec3::new(chip_extents.x / 2.0, 0.0, 0.01),
-            ))
-            .with_children(|pc| {
-                pc.spawn(PinLabelBundle::new(
-                    output_pin_model.label.clone(),
-                    TextColor(Color::BLACK),
-                    TextFont {
-                        font: common_assets.font.clone(),
-                        font_size: render_settings.chip_pin_label_font_size,
-                        ..default()
-                    },
-                    Vec3::new(-12.0, 0.0, 0.2),
-                ));
-            });
+        // Output pins, a single output is centered
+        let num_outputs = pin_model_collection.num_outputs();
+        for (i, pin_model) in pin_model_collection.iter_outputs().enumerate() {
+            let y = match num_outputs {
+                1 => 0.0,
+                _ => ((i as f32 + 0.75) * render_settings.chip_pin_gap) - (chip_extents.y / 2.0),
+            };
+
+            pin_collection
+                .spawn(GenericChipOutputPinBundle::new(
+                    render_settings,
+                    pin_model.uuid,
+                    Vec3::new(chip_extents.x / 2.0, y, 0.01),
+                ))
+                .with_children(|pc| {
+                    pc.spawn(PinLabelBundle::new(
+                        pin_model.label.clone(),
+                        TextColor(Color::BLACK),
+                        TextFont {
+                            font: common_assets.font.clone(),
+                            font_size: render_settings.chip_pin_label_font_size,
+                            ..default()
+                        },
+                        Vec3::new(-12.0, 0.0, 0.2),
+                    ));
+                });
+        }
     }
 }
 
@@ -280,7 +287,7 @@ impl BuildView<DeviceViewKind> for GenericChip {
 }
 
 /// Calculates the chip extents based on the amount of input/output pins.
-fn calculate_chip_extents(
+pub fn calculate_chip_extents(
     render_settings: &CircuitBoardRenderingSettings,
     num_inputs: usize,
     num_outputs: usize,
@@ -1,11 +1,15 @@
 use bevy::prelude::*;
 
-use crate::designer::{
-    pin::{PinModel, PinModelCollection},
-    position::Position,
+use crate::designer::{position::Position, save_management::format::DeviceProperties};
+
+use super::{
+    device::{Device, DeviceCategory},
+    flipflop::FlipFlopOptions,
+    generic_chip::GenericChipBundle,
 };
 
-use super::{device::Device, generic_chip::GenericChipBundle};
+/// Inputs from bottom to top.
+const INPUTS: [&str; 3] = ["K", "C", "J"];
 
 #[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
@@ -15,14 +19,10 @@ impl Device for JKFlipFlop {
     fn create_bundle(position: Position) -> impl Bundle {
         (
             JKFlipFlop,
+            FlipFlopOptions::default(),
             GenericChipBundle::new(
                 position,
-                PinModelCollection(vec![
-                    PinModel::new_input("K".into()),
-                    PinModel::new_input("C".into()),
-                    PinModel::new_input("J".into()),
-                    PinModel::new_output("Q".into()),
-                ]),
+                FlipFlopOptions::default().pin_model_collection(&INPUTS),
                 Self::device_id().into(),
             ),
         )
@@ -31,4 +31,16 @@ impl Device for JKFlipFlop {
     fn device_id() -> &'static str {
         "JK-FF"
     }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::FlipFlops
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        FlipFlopOptions::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        FlipFlopOptions::load_properties(device, properties, &INPUTS);
+    }
 }
@@ -0,0 +1,186 @@
+//! Inputs that can be operated with the keyboard, so circuits can be played with without clicking.
+
+use bevy::prelude::*;
+
+use crate::{
+    designer::{
+        pin::PinModelCollection,
+        save_management::format::{DeviceProperties, PropertyValue},
+        signal::Signal,
+    },
+    ui::cursor_captured::IsKeyboardCaptured,
+};
+
+use super::binary_io::{BinarySwitch, PushButton};
+
+/// Keys that can be bound with their names in save files.
+/// Keys of the editor's single key shortcuts are left out, see [`RESERVED_KEYS`].
+const BINDABLE_KEYS: [(KeyCode, &str); 41] = [
+    (KeyCode::KeyA, "A"),
+    (KeyCode::KeyB, "B"),
+    (KeyCode::KeyC, "C"),
+    (KeyCode::KeyE, "E"),
+    (KeyCode::KeyF, "F"),
+    (KeyCode::KeyG, "G"),
+    (KeyCode::KeyH, "H"),
+    (KeyCode::KeyI, "I"),
+    (KeyCode::KeyJ, "J"),
+    (KeyCode::KeyK, "K"),
+    (KeyCode::KeyL, "L"),
+    (KeyCode::KeyM, "M"),
+    (KeyCode::KeyN, "N"),
+    (KeyCode::KeyO, "O"),
+    (KeyCode::KeyP, "P"),
+    (KeyCode::KeyQ, "Q"),
+    (KeyCode::KeyR, "R"),
+    (KeyCode::KeyS, "S"),
+    (KeyCode::KeyT, "T"),
+    (KeyCode::KeyU, "U"),
+    (KeyCode::KeyV, "V"),
+    (KeyCode::KeyW, "W"),
+    (KeyCode::KeyX, "X"),
+    (KeyCode::KeyY, "Y"),
+    (KeyCode::KeyZ, "Z"),
+    (KeyCode::Digit0, "0"),
+    (KeyCode::Digit1, "1"),
+    (KeyCode::Digit2, "2"),
+    (KeyCode::Digit3, "3"),
+    (KeyCode::Digit4, "4"),
+    (KeyCode::Digit5, "5"),
+    (KeyCode::Digit6, "6"),
+    (KeyCode::Digit7, "7"),
+    (KeyCode::Digit8, "8"),
+    (KeyCode::Digit9, "9"),
+    (KeyCode::Enter, "Enter"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::ArrowUp, "Up"),
+    (KeyCode::ArrowDown, "Down"),
+    (KeyCode::ArrowLeft, "Left"),
+    (KeyCode::ArrowRight, "Right"),
+];
+
+/// Keys that are used by shortcuts without modifiers and what they do.
+const RESERVED_KEYS: [(&str, &str); 3] = [
+    ("D", "toggling the debug mode"),
+    ("Space", "pausing the simulation"),
+    ("Delete", "deleting the selection"),
+];
+
+/// Bound keys are ignored while one of these is held, so shortcuts like Ctrl+C don't operate inputs.
+const SHORTCUT_MODIFIERS: [KeyCode; 6] = [
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+/// The key that operates an input device, switches toggle when it is pressed
+/// and push-buttons are high while it is held.
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct KeyBinding(pub Option<KeyCode>);
+
+impl KeyBinding {
+    pub fn key_name(&self) -> &'static str {
+        self.0
+            .and_then(|key_code| {
+                BINDABLE_KEYS
+                    .iter()
+                    .find(|(bindable_key, _)| *bindable_key == key_code)
+            })
+            .map(|(_, name)| *name)
+            .unwrap_or_default()
+    }
+
+    /// Finds the key by its name ignoring case, an empty name removes the binding.
+    pub fn from_key_name(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Ok(KeyBinding(None));
+        }
+
+        if let Some((_, usage)) = RESERVED_KEYS
+            .iter()
+            .find(|(reserved_name, _)| reserved_name.eq_ignore_ascii_case(name))
+        {
+            return Err(format!("\"{}\" is reserved for {}", name, usage));
+        }
+
+        BINDABLE_KEYS
+            .iter()
+            .find(|(_, bindable_name)| bindable_name.eq_ignore_ascii_case(name))
+            .map(|(key_code, _)| KeyBinding(Some(*key_code)))
+            .ok_or_else(|| format!("\"{}\" is not a key that can be bound", name))
+    }
+
+    pub fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(key_binding) = device.get::<KeyBinding>() {
+            properties.set("key", PropertyValue::Text(key_binding.key_name().into()));
+        }
+        properties
+    }
+
+    pub fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        let Some(key_name) = properties.get_text("key") else {
+            return;
+        };
+
+        match KeyBinding::from_key_name(key_name) {
+            Ok(key_binding) => {
+                device.insert(key_binding);
+            }
+            Err(error) => warn!("Ignoring key binding, {}", error),
+        }
+    }
+}
+
+pub fn toggle_bound_switches(
+    input: Res<ButtonInput<KeyCode>>,
+    keyboard_captured: Res<IsKeyboardCaptured>,
+    mut q_switches: Query<(&mut PinModelCollection, &KeyBinding), With<BinarySwitch>>,
+) {
+    if keyboard_captured.0 || input.any_pressed(SHORTCUT_MODIFIERS) {
+        return;
+    }
+
+    for (mut pin_model_collection, key_binding) in q_switches.iter_mut() {
+        let Some(key_code) = key_binding.0 else {
+            continue;
+        };
+
+        if input.just_pressed(key_code) {
+            let current_signal = pin_model_collection["Q"].signal_state.get_signal().clone();
+            pin_model_collection["Q"]
+                .signal_state
+                .set_signal(current_signal.negate());
+        }
+    }
+}
+
+/// Push-buttons are high while they are held with the cursor or their bound key.
+pub fn update_push_buttons(
+    input: Res<ButtonInput<KeyCode>>,
+    keyboard_captured: Res<IsKeyboardCaptured>,
+    mut q_push_buttons: Query<(&mut PinModelCollection, &PushButton, &KeyBinding)>,
+) {
+    let keys_blocked = keyboard_captured.0 || input.any_pressed(SHORTCUT_MODIFIERS);
+
+    for (mut pin_model_collection, push_button, key_binding) in q_push_buttons.iter_mut() {
+        let key_held = !keys_blocked
+            && key_binding
+                .0
+                .is_some_and(|key_code| input.pressed(key_code));
+
+        let next_signal = Signal::from(push_button.held_by_cursor || key_held);
+
+        // only set on changes, otherwise the previous signal is lost for edge detection
+        if *pin_model_collection["Q"].signal_state.get_signal() != next_signal {
+            pin_model_collection["Q"]
+                .signal_state
+                .set_signal(next_signal);
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Addressable memory devices.
+//! The contents are saved with the board as a hex string, see [`MemoryContents::to_hex`].
+
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    position::Position,
+    save_management::format::{DeviceProperties, PropertyValue},
+};
+
+use super::{
+    bit_width::{numbered_pins, BitWidth},
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
+
+/// Limits the size of memories to 4096 words.
+const MAX_ADDRESS_WIDTH: u32 = 12;
+
+/// The words of a memory, one per address.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct MemoryContents {
+    pub address_width: u32,
+    pub words: Vec<u64>,
+}
+
+impl Default for MemoryContents {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl MemoryContents {
+    pub fn new(address_width: u32) -> Self {
+        Self {
+            address_width,
+            words: vec![0; 1 << address_width],
+        }
+    }
+
+    pub fn read(&self, address: u64) -> u64 {
+        self.words
+            .get(address as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn write(&mut self, address: u64, value: u64) {
+        if let Some(word) = self.words.get_mut(address as usize) {
+            *word = value;
+        }
+    }
+
+    /// Replaces the contents from address 0 on, words that do not fit are dropped.
+    /// Returns the amount of dropped words.
+    pub fn load(&mut self, words: &[u64], bit_width: BitWidth) -> usize {
+        self.words.fill(0);
+
+        for (word, value) in self.words.iter_mut().zip(words) {
+            *word = value & bit_width.mask();
+        }
+
+        words.len().saturating_sub(self.words.len())
+    }
+
+    /// Amount of hex digits per word.
+    pub fn hex_digits(bit_width: BitWidth) -> usize {
+        bit_width.0.div_ceil(4) as usize
+    }
+
+    /// Writes all words as fixed width hex digits without separators.
+    /// Zeros at the end are left out, so mostly empty memories stay small in save files.
+    pub fn to_hex(&self, bit_width: BitWidth) -> String {
+        let digits = Self::hex_digits(bit_width);
+        let used_words = self
+            .words
+            .iter()
+            .rposition(|&word| word != 0)
+            .map_or(0, |last| last + 1);
+
+        self.words[..used_words]
+            .iter()
+            .map(|word| format!("{:0digits$x}", word, digits = digits))
+            .collect()
+    }
+
+    /// Reads the words written by [`MemoryContents::to_hex`].
+    pub fn from_hex(hex: &str, address_width: u32, bit_width: BitWidth) -> Result<Self, String> {
+        let digits = Self::hex_digits(bit_width);
+        let hex: Vec<char> = hex.chars().filter(|c| !c.is_whitespace()).collect();
+
+        let words = hex
+            .chunks(digits)
+            .map(|chunk| {
+                let chunk: String = chunk.iter().collect();
+                u64::from_str_radix(&chunk, 16).map_err(|error| format!("\"{}\": {}", chunk, error))
+            })
+            .collect::<Result<Vec<u64>, String>>()?;
+
+        let mut contents = Self::new(address_width);
+        contents.load(&words, bit_width);
+        Ok(contents)
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = BitWidth::save_properties(device);
+        if let Some(contents) = device.get::<MemoryContents>() {
+            let bit_width = device.get::<BitWidth>().copied().unwrap_or_default();
+
+            properties.set(
+                "address_width",
+                PropertyValue::Int(contents.address_width as i64),
+            );
+            properties.set("contents", PropertyValue::Text(contents.to_hex(bit_width)));
+        }
+        properties
+    }
+
+    /// Inserts the widths and contents and rebuilds the pins to match them.
+    fn load_properties(
+        device: &mut EntityWorldMut,
+        properties: &DeviceProperties,
+        create_pins: fn(u32, BitWidth) -> PinModelCollection,
+    ) {
+        let address_width = properties
+            .get_int("address_width")
+            .map(|address_width| address_width.clamp(1, MAX_ADDRESS_WIDTH as i64) as u32)
+            .unwrap_or(MemoryContents::default().address_width);
+
+        let bit_width = BitWidth::from_properties(properties).unwrap_or_default();
+
+        let contents = match MemoryContents::from_hex(
+            properties.get_text("contents").unwrap_or_default(),
+            address_width,
+            bit_width,
+        ) {
+            Ok(contents) => contents,
+            Err(error) => {
+                warn!("Ignoring broken memory contents, {}", error);
+                MemoryContents::new(address_width)
+            }
+        };
+
+        device.insert((create_pins(address_width, bit_width), bit_width, contents));
+    }
+}
+
+/// Outputs the word at the address `A*` on `D*`.
+/// The contents are edited in the memory editor or loaded from a file.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Rom;
+
+impl Rom {
+    fn create_pins(address_width: u32, bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = numbered_pins("A", address_width, PinModel::new_input);
+        pin_models.extend(numbered_pins("D", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Rom {
+    fn create_bundle(position: Position) -> impl Bundle {
+        let contents = MemoryContents::default();
+
+        (
+            Rom,
+            BitWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(contents.address_width, BitWidth::default()),
+                Self::device_id().into(),
+            ),
+            contents,
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "ROM"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Memory
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        MemoryContents::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        MemoryContents::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Outputs the word at the address `A*` on `DO*` and stores `DI*` at it on the rising edge of `C`
+/// while `WE` is high.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Ram;
+
+impl Ram {
+    fn create_pins(address_width: u32, bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = vec![
+            PinModel::new_input("C".into()),
+            PinModel::new_input("WE".into()),
+        ];
+        pin_models.extend(numbered_pins("A", address_width, PinModel::new_input));
+        pin_models.extend(numbered_pins("DI", bit_width.0, PinModel::new_input));
+        pin_models.extend(numbered_pins("DO", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Ram {
+    fn create_bundle(position: Position) -> impl Bundle {
+        let contents = MemoryContents::default();
+
+        (
+            Ram,
+            BitWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(contents.address_width, BitWidth::default()),
+                Self::device_id().into(),
+            ),
+            contents,
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "RAM"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Memory
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        MemoryContents::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        MemoryContents::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_round_trip() {
+        let bit_width = BitWidth(12);
+        let mut contents = MemoryContents::new(4);
+        contents.load(&[0xabc, 0x001, 0x000, 0xfff, 0x010], bit_width);
+
+        let hex = contents.to_hex(bit_width);
+        // trailing zero words are left out
+        assert_eq!(hex, "abc001000fff010");
+
+        let restored = MemoryContents::from_hex(&hex, 4, bit_width).unwrap();
+        assert_eq!(restored.address_width, contents.address_width);
+        assert_eq!(restored.words, contents.words);
+    }
+
+    #[test]
+    fn from_hex_rejects_invalid_digits() {
+        assert!(MemoryContents::from_hex("0g", 4, BitWidth(8)).is_err());
+    }
+}
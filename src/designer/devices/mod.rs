@@ -1,28 +1,67 @@
 pub mod and_2;
+pub mod annotation;
+pub mod arithmetic;
 pub mod binary_io;
+pub mod bit_width;
 pub mod clock;
+pub mod constants;
 pub mod d_flipflop;
+pub mod d_latch;
 pub mod device;
+pub mod displays;
+pub mod drive;
+pub mod flipflop;
 pub mod generic_chip;
 pub mod jk_flipflop;
+pub mod key_binding;
+pub mod memory;
 pub mod nand_2;
+pub mod net_label;
 pub mod not;
 pub mod or_2;
+pub mod peripherals;
+pub mod plexers;
+pub mod random;
+pub mod registers;
+pub mod sr_latch;
 pub mod t_flipflop;
 pub mod xor_2;
 
 use and_2::And2;
+use annotation::{NamedFrame, StickyNote, TextAnnotation};
+use arithmetic::{Adder, AdderSubtractor, BarrelShifter, Comparator, Multiplier};
 use bevy::prelude::*;
-use binary_io::{toggle_binary_switch, update_board_binary_displays, BinaryDisplay, BinarySwitch};
+use binary_io::{
+    press_push_buttons, toggle_binary_switch, update_board_binary_displays, BinaryDisplay,
+    BinarySwitch, PushButton,
+};
+use bit_width::BitWidth;
 use clock::{tick_clocks, Clock};
+use constants::{ConstantHigh, ConstantLow};
 use d_flipflop::DFlipFlop;
+use d_latch::DLatch;
 use device::{update_device_positions, DeviceModel, DeviceViewKind, RegisterDevice};
+use displays::{
+    update_leds, update_segment_displays, HexDigitDisplay, Led, LedMatrix, SevenSegmentDisplay,
+};
+use drive::{OpenDrainNand2, OpenDrainNot, OutputDrive, PullDown, PullUp};
+use flipflop::FlipFlopOptions;
 use generic_chip::GenericChip;
 use jk_flipflop::JKFlipFlop;
+use key_binding::{toggle_bound_switches, update_push_buttons, KeyBinding};
+use memory::{MemoryContents, Ram, Rom};
 use moonshine_view::RegisterView;
 use nand_2::Nand2;
+use net_label::{
+    spawn_net_name_tooltip, update_net_label_texts, update_net_name_tooltip, NetLabel,
+};
 use not::Not;
 use or_2::Or2;
+use peripherals::{Keyboard, KeyboardBuffer, Terminal, TerminalOutput};
+use plexers::{Decoder, Demultiplexer, Multiplexer, PriorityEncoder, SelectWidth};
+use random::{Lfsr, LfsrTaps, PseudoRandom, RandomSource};
+use registers::{Counter, Register, ShiftRegister, StoredValue};
+use sr_latch::SRLatch;
 use t_flipflop::TFlipFlop;
 use xor_2::Xor2;
 
@@ -38,37 +77,115 @@ impl Plugin for DevicePlugin {
             .register_type::<Position>()
             .register_type::<BinarySwitch>()
             .register_type::<BinaryDisplay>()
+            .register_type::<PushButton>()
+            .register_type::<KeyBinding>()
             .register_type::<GenericChip>()
             .register_type::<PinModelCollection>()
-            .register_type::<Clock>();
+            .register_type::<Clock>()
+            .register_type::<FlipFlopOptions>()
+            .register_type::<OutputDrive>()
+            .register_type::<SelectWidth>()
+            .register_type::<BitWidth>()
+            .register_type::<StoredValue>()
+            .register_type::<MemoryContents>()
+            .register_type::<PseudoRandom>()
+            .register_type::<LfsrTaps>()
+            .register_type::<TerminalOutput>()
+            .register_type::<KeyboardBuffer>()
+            .register_type::<SevenSegmentDisplay>()
+            .register_type::<HexDigitDisplay>()
+            .register_type::<Led>()
+            .register_type::<LedMatrix>()
+            .register_type::<NetLabel>()
+            .register_type::<TextAnnotation>()
+            .register_type::<StickyNote>()
+            .register_type::<NamedFrame>();
 
         app.add_view::<DeviceViewKind, BinarySwitch>()
             .add_view::<DeviceViewKind, BinaryDisplay>()
+            .add_view::<DeviceViewKind, PushButton>()
             .add_view::<DeviceViewKind, GenericChip>()
-            .add_view::<DeviceViewKind, Clock>();
+            .add_view::<DeviceViewKind, Clock>()
+            .add_view::<DeviceViewKind, NetLabel>()
+            .add_view::<DeviceViewKind, SevenSegmentDisplay>()
+            .add_view::<DeviceViewKind, HexDigitDisplay>()
+            .add_view::<DeviceViewKind, Led>()
+            .add_view::<DeviceViewKind, LedMatrix>()
+            .add_view::<DeviceViewKind, TextAnnotation>()
+            .add_view::<DeviceViewKind, StickyNote>()
+            .add_view::<DeviceViewKind, NamedFrame>();
 
-        app.register_device::<And2>()
-            .register_device::<Nand2>()
-            .register_device::<Or2>()
-            .register_device::<Xor2>()
-            .register_device::<Not>()
-            .register_device::<Clock>()
-            .register_device::<JKFlipFlop>()
-            .register_device::<DFlipFlop>()
-            .register_device::<TFlipFlop>()
-            .register_device::<BinaryDisplay>()
-            .register_device::<BinarySwitch>();
+        register_devices(app);
 
         app.add_systems(Update, tick_clocks)
             .add_systems(
                 Update,
                 (
                     toggle_binary_switch,
-                    update_board_binary_displays
+                    toggle_bound_switches,
+                    (press_push_buttons, update_push_buttons).chain(),
+                    (
+                        update_board_binary_displays,
+                        update_segment_displays,
+                        update_leds,
+                    )
                         .chain()
                         .after(propagate_signals),
                 ), //TODO: observers?
             )
-            .add_systems(Update, update_device_positions);
+            .add_systems(Update, update_device_positions)
+            .add_systems(Startup, spawn_net_name_tooltip)
+            .add_systems(Update, (update_net_label_texts, update_net_name_tooltip));
     }
 }
+
+/// Registers every device, so it can be spawned, saved and loaded.
+/// Also used by the headless design check, which does not build any views.
+pub fn register_devices(app: &mut App) {
+    app.register_device::<And2>()
+        .register_device::<Nand2>()
+        .register_device::<Or2>()
+        .register_device::<Xor2>()
+        .register_device::<Not>()
+        .register_device::<OpenDrainNand2>()
+        .register_device::<OpenDrainNot>()
+        .register_device::<Clock>()
+        .register_device::<JKFlipFlop>()
+        .register_device::<DFlipFlop>()
+        .register_device::<TFlipFlop>()
+        .register_device::<SRLatch>()
+        .register_device::<DLatch>()
+        .register_device::<Multiplexer>()
+        .register_device::<Demultiplexer>()
+        .register_device::<Decoder>()
+        .register_device::<PriorityEncoder>()
+        .register_device::<Adder>()
+        .register_device::<AdderSubtractor>()
+        .register_device::<Comparator>()
+        .register_device::<Multiplier>()
+        .register_device::<BarrelShifter>()
+        .register_device::<Register>()
+        .register_device::<Counter>()
+        .register_device::<ShiftRegister>()
+        .register_device::<RandomSource>()
+        .register_device::<Lfsr>()
+        .register_device::<Rom>()
+        .register_device::<Ram>()
+        .register_device::<BinaryDisplay>()
+        .register_device::<BinarySwitch>()
+        .register_device::<PushButton>()
+        .register_device::<ConstantHigh>()
+        .register_device::<ConstantLow>()
+        .register_device::<PullUp>()
+        .register_device::<PullDown>()
+        .register_device::<Terminal>()
+        .register_device::<Keyboard>()
+        .register_device::<SevenSegmentDisplay>()
+        .register_device::<HexDigitDisplay>()
+        .register_device::<Led>()
+        .register_device::<LedMatrix>()
+        .register_device::<NetLabel>()
+        .register_device::<TextAnnotation>()
+        .register_device::<StickyNote>()
+        .register_device::<NamedFrame>();
+}
@@ -5,7 +5,10 @@ use crate::designer::{
     position::Position,
 };
 
-use super::{device::Device, generic_chip::GenericChipBundle};
+use super::{
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
 
 #[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
@@ -30,4 +33,8 @@ impl Device for Nand2 {
     fn device_id() -> &'static str {
         "NAND-2"
     }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Gates
+    }
 }
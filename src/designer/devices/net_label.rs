@@ -0,0 +1,380 @@
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+use bevy::prelude::*;
+use bevy_prototype_lyon::prelude::*;
+use moonshine_core::prelude::*;
+use moonshine_view::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    assets::common_assets::CommonAssets,
+    designer::{
+        bounding_box::BoundingBox,
+        cursor::Cursor,
+        pin::{PinModel, PinModelCollection, PinViewBundle, PinViewCollectionBundle},
+        position::Position,
+        render_settings::CircuitBoardRenderingSettings,
+        save_management::format::{DeviceProperties, PropertyValue},
+        wire::{WireModel, WireNode, WireNodes, WireView},
+    },
+    find_descendant, get_cursor,
+};
+
+use super::device::{Device, DeviceCategory, DeviceModelBundle, DeviceViewBundle, DeviceViewKind};
+
+/// A label that connects its pin to the pins of all other net labels with the same name,
+/// without needing a drawn wire between them.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct NetLabel {
+    pub name: String,
+}
+
+impl Device for NetLabel {
+    fn create_bundle(position: Position) -> impl Bundle {
+        NetLabelBundle::new(position, "NET".into())
+    }
+
+    fn device_id() -> &'static str {
+        "NET-LABEL"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(net_label) = device.get::<NetLabel>() {
+            properties.set("name", PropertyValue::Text(net_label.name.clone()));
+        }
+        properties
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        if let Some(name) = properties.get_text("name") {
+            device.insert(NetLabel { name: name.into() });
+        }
+    }
+}
+
+#[derive(Bundle, Clone)]
+pub struct NetLabelBundle {
+    net_label: NetLabel,
+    device_model_bundle: DeviceModelBundle,
+    pin_model_collection: PinModelCollection,
+}
+
+impl NetLabelBundle {
+    fn new(position: Position, name: String) -> Self {
+        Self {
+            net_label: NetLabel { name },
+            device_model_bundle: DeviceModelBundle::new(position),
+            pin_model_collection: PinModelCollection(vec![PinModel::new_input("N".into())]),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct NetLabelBody;
+
+#[derive(Bundle)]
+pub struct NetLabelBodyBundle {
+    net_label_body: NetLabelBody,
+    fill: Fill,
+    stroke: Stroke,
+    shape_bundle: ShapeBundle,
+}
+
+impl NetLabelBodyBundle {
+    fn new(render_settings: &CircuitBoardRenderingSettings) -> Self {
+        let half_extents = render_settings.net_label_extents / 2.0;
+
+        // tag shape pointing towards the pin
+        let points = vec![
+            Vec2::new(-half_extents.x, 0.0),
+            Vec2::new(-half_extents.x + half_extents.y, half_extents.y),
+            Vec2::new(half_extents.x, half_extents.y),
+            Vec2::new(half_extents.x, -half_extents.y),
+            Vec2::new(-half_extents.x + half_extents.y, -half_extents.y),
+        ];
+
+        Self {
+            net_label_body: NetLabelBody,
+            fill: Fill::color(render_settings.net_label_color),
+            stroke: Stroke::new(
+                render_settings.device_stroke_color,
+                render_settings.device_stroke_width,
+            ),
+            shape_bundle: ShapeBundle {
+                path: GeometryBuilder::build_as(&shapes::Polygon {
+                    points,
+                    closed: true,
+                }),
+                ..default()
+            },
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct NetLabelText;
+
+#[derive(Bundle)]
+pub struct NetLabelTextBundle {
+    net_label_text: NetLabelText,
+    text_2d: Text2d,
+    text_color: TextColor,
+    text_font: TextFont,
+    text_layout: TextLayout,
+    transform: Transform,
+}
+
+impl NetLabelTextBundle {
+    fn new(
+        name: String,
+        render_settings: &CircuitBoardRenderingSettings,
+        common_assets: &CommonAssets,
+    ) -> Self {
+        Self {
+            net_label_text: NetLabelText,
+            text_2d: Text2d::new(name),
+            text_color: TextColor(Color::BLACK),
+            text_font: TextFont {
+                font: common_assets.font.clone(),
+                font_size: render_settings.net_label_font_size,
+                ..default()
+            },
+            text_layout: TextLayout::new_with_justify(JustifyText::Center),
+            transform: Transform::from_xyz(render_settings.net_label_extents.y / 4.0, 0.0, 0.01),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct NetLabelPin;
+
+#[derive(Bundle)]
+pub struct NetLabelPinBundle {
+    net_label_pin: NetLabelPin,
+    pin_view_bundle: PinViewBundle,
+}
+
+impl NetLabelPinBundle {
+    fn new(render_settings: &CircuitBoardRenderingSettings, uuid: Uuid) -> Self {
+        Self {
+            net_label_pin: NetLabelPin,
+            pin_view_bundle: PinViewBundle::new(
+                render_settings,
+                uuid,
+                render_settings.device_io_pin_radius,
+                Vec3::new(-render_settings.net_label_extents.x / 2.0, 0.0, 0.02),
+            ),
+        }
+    }
+}
+
+#[derive(Component)]
+struct NetLabelPinCollection;
+
+#[derive(Bundle)]
+struct NetLabelPinCollectionBundle {
+    net_label_pin_collection: NetLabelPinCollection,
+    pin_collection_bundle: PinViewCollectionBundle,
+}
+
+impl NetLabelPinCollectionBundle {
+    fn new() -> Self {
+        Self {
+            net_label_pin_collection: NetLabelPinCollection,
+            pin_collection_bundle: PinViewCollectionBundle::new(),
+        }
+    }
+}
+
+impl BuildView<DeviceViewKind> for NetLabel {
+    fn build(
+        world: &World,
+        object: Object<DeviceViewKind>,
+        mut view: ViewCommands<DeviceViewKind>,
+    ) {
+        let common_assets = world.resource::<CommonAssets>();
+        let render_settings = world.resource::<CircuitBoardRenderingSettings>();
+
+        let position = world.get::<Position>(object.entity()).unwrap();
+        let pin_model_collection = world.get::<PinModelCollection>(object.entity()).unwrap();
+        let net_label = world.get::<NetLabel>(object.entity()).unwrap();
+
+        view.insert(DeviceViewBundle::new(
+            position.clone(),
+            render_settings.net_label_extents,
+        ))
+        .with_children(|device| {
+            device.spawn(NetLabelBodyBundle::new(render_settings));
+            device.spawn(NetLabelTextBundle::new(
+                net_label.name.clone(),
+                render_settings,
+                common_assets,
+            ));
+
+            device
+                .spawn(NetLabelPinCollectionBundle::new())
+                .with_children(|pc| {
+                    pc.spawn(NetLabelPinBundle::new(
+                        render_settings,
+                        pin_model_collection["N"].uuid,
+                    ));
+                });
+        });
+    }
+}
+
+pub fn update_net_label_texts(
+    q_net_labels: Query<(&NetLabel, &Viewable<DeviceViewKind>), Changed<NetLabel>>,
+    q_children: Query<&Children>,
+    mut q_texts: Query<&mut Text2d, With<NetLabelText>>,
+) {
+    for (net_label, viewable) in q_net_labels.iter() {
+        let view_entity = viewable.view().entity();
+
+        find_descendant!(q_children, view_entity, q_texts, |target: &mut Text2d| {
+            target.0 = net_label.name.clone();
+        });
+    }
+}
+
+/// Maps the pin uuid of every net label to the name of its net.
+pub fn collect_net_label_pins<'a>(
+    net_labels: impl Iterator<Item = (&'a NetLabel, &'a PinModelCollection)>,
+) -> HashMap<Uuid, String> {
+    net_labels
+        .flat_map(|(net_label, pin_model_collection)| {
+            pin_model_collection
+                .iter()
+                .map(|pin_model| (pin_model.uuid, net_label.name.clone()))
+        })
+        .collect()
+}
+
+/// Collects the names of all net labels that are connected to the given wire by drawn wires.
+pub fn connected_net_names(
+    wire_entity: Entity,
+    wires: &[(Entity, &WireNodes)],
+    net_label_pins: &HashMap<Uuid, String>,
+) -> BTreeSet<String> {
+    let mut net_names = BTreeSet::new();
+    let mut visited: HashSet<Entity> = HashSet::new();
+    let mut queue = VecDeque::from([wire_entity]);
+
+    while let Some(current_wire) = queue.pop_front() {
+        if !visited.insert(current_wire) {
+            continue;
+        }
+
+        let Some((_, wire_nodes)) = wires.iter().find(|(entity, _)| *entity == current_wire) else {
+            continue;
+        };
+
+        for wire_node in wire_nodes.0.iter() {
+            if let WireNode::Pin(pin_uuid) = wire_node {
+                if let Some(net_name) = net_label_pins.get(pin_uuid) {
+                    net_names.insert(net_name.clone());
+                }
+            }
+
+            wires
+                .iter()
+                .filter(|(entity, other_nodes)| {
+                    !visited.contains(entity) && other_nodes.0.contains(wire_node)
+                })
+                .for_each(|(entity, _)| queue.push_back(*entity));
+        }
+    }
+
+    net_names
+}
+
+/// Text next to the cursor showing the net names of the hovered wire.
+#[derive(Component)]
+pub struct NetNameTooltip;
+
+#[derive(Bundle)]
+pub struct NetNameTooltipBundle {
+    net_name_tooltip: NetNameTooltip,
+    text_2d: Text2d,
+    text_color: TextColor,
+    text_font: TextFont,
+    text_layout: TextLayout,
+    transform: Transform,
+    visibility: Visibility,
+}
+
+impl NetNameTooltipBundle {
+    fn new(render_settings: &CircuitBoardRenderingSettings, common_assets: &CommonAssets) -> Self {
+        Self {
+            net_name_tooltip: NetNameTooltip,
+            text_2d: Text2d::default(),
+            text_color: TextColor(Color::BLACK),
+            text_font: TextFont {
+                font: common_assets.font.clone(),
+                font_size: render_settings.net_label_font_size,
+                ..default()
+            },
+            text_layout: TextLayout::new_with_justify(JustifyText::Left),
+            transform: Transform::default(),
+            visibility: Visibility::Hidden,
+        }
+    }
+}
+
+pub fn spawn_net_name_tooltip(
+    mut commands: Commands,
+    render_settings: Res<CircuitBoardRenderingSettings>,
+    common_assets: Res<CommonAssets>,
+) {
+    commands.spawn(NetNameTooltipBundle::new(&render_settings, &common_assets));
+}
+
+/// Shows the names of the nets the hovered wire belongs to next to the cursor.
+#[allow(clippy::type_complexity)]
+pub fn update_net_name_tooltip(
+    q_cursor: Query<&Transform, (With<Cursor>, Without<NetNameTooltip>)>,
+    q_wire_views: Query<(&View<WireModel>, &BoundingBox), With<WireView>>,
+    q_wires: Query<(Entity, &WireNodes), With<WireModel>>,
+    q_net_labels: Query<(&NetLabel, &PinModelCollection)>,
+    mut q_tooltip: Query<(&mut Text2d, &mut Transform, &mut Visibility), With<NetNameTooltip>>,
+) {
+    let cursor_position = get_cursor!(q_cursor).translation.truncate();
+
+    let Ok((mut tooltip_text, mut tooltip_transform, mut tooltip_visibility)) =
+        q_tooltip.get_single_mut()
+    else {
+        return;
+    };
+
+    let hovered_wire = q_wire_views
+        .iter()
+        .find(|(_, bbox)| bbox.point_in_bbox(cursor_position))
+        .map(|(view, _)| view.viewable().entity());
+
+    let net_names = hovered_wire
+        .map(|wire_entity| {
+            let wires: Vec<(Entity, &WireNodes)> = q_wires.iter().collect();
+            let net_label_pins = collect_net_label_pins(q_net_labels.iter());
+            connected_net_names(wire_entity, &wires, &net_label_pins)
+        })
+        .unwrap_or_default();
+
+    if net_names.is_empty() {
+        *tooltip_visibility = Visibility::Hidden;
+        return;
+    }
+
+    let text = net_names.into_iter().collect::<Vec<String>>().join(", ");
+    if tooltip_text.0 != text {
+        tooltip_text.0 = text;
+    }
+
+    tooltip_transform.translation = (cursor_position + Vec2::new(10.0, 15.0)).extend(3.0);
+    *tooltip_visibility = Visibility::Visible;
+}
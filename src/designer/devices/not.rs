@@ -5,7 +5,10 @@ use crate::designer::{
     position::Position,
 };
 
-use super::{device::Device, generic_chip::GenericChipBundle};
+use super::{
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
 
 #[derive(Component, Reflect, Clone)]
 #[reflect(Component)]
@@ -29,4 +32,8 @@ impl Device for Not {
     fn device_id() -> &'static str {
         "NOT"
     }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Gates
+    }
 }
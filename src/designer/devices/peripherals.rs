@@ -0,0 +1,138 @@
+//! Text input and output, to build small computers that talk to the user.
+//! Both exchange ASCII bytes over an 8 bit data bus and have a panel to show or type the text.
+//! The text is not saved with the board.
+
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    position::Position,
+};
+
+use super::{
+    bit_width::numbered_pins,
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
+
+/// Width of the data bus of the peripherals.
+pub const DATA_WIDTH: u32 = 8;
+
+/// Older text is dropped once the terminal holds more characters.
+const MAX_TERMINAL_LENGTH: usize = 4096;
+
+/// Characters typed while the buffer is full are dropped.
+const MAX_KEYBOARD_BUFFER: usize = 64;
+
+pub const BACKSPACE: u8 = 0x08;
+
+/// Text written to a terminal.
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component)]
+pub struct TerminalOutput {
+    pub text: String,
+}
+
+impl TerminalOutput {
+    /// Appends a printable character or a newline, a backspace removes the last character.
+    /// Other control characters are ignored.
+    pub fn write(&mut self, byte: u8) {
+        match byte {
+            BACKSPACE => {
+                self.text.pop();
+            }
+            b'\n' | b' '..=b'~' => self.text.push(byte as char),
+            _ => return,
+        }
+
+        if self.text.len() > MAX_TERMINAL_LENGTH {
+            let excess = self.text.len() - MAX_TERMINAL_LENGTH;
+            self.text.drain(..excess);
+        }
+    }
+}
+
+/// Characters typed into a keyboard that have not been read yet, oldest first.
+#[derive(Component, Reflect, Clone, Default)]
+#[reflect(Component)]
+pub struct KeyboardBuffer {
+    pub characters: Vec<u8>,
+}
+
+impl KeyboardBuffer {
+    pub fn push(&mut self, byte: u8) {
+        if self.characters.len() < MAX_KEYBOARD_BUFFER {
+            self.characters.push(byte);
+        }
+    }
+}
+
+/// Appends the ASCII byte on `D*` on a clock edge while `WE` is high, `CLR` clears the text.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Terminal;
+
+impl Device for Terminal {
+    fn create_bundle(position: Position) -> impl Bundle {
+        let mut pin_models = vec![
+            PinModel::new_input("CLR".into()),
+            PinModel::new_input("C".into()),
+            PinModel::new_input("WE".into()),
+        ];
+        pin_models.extend(numbered_pins("D", DATA_WIDTH, PinModel::new_input));
+
+        (
+            Terminal,
+            TerminalOutput::default(),
+            GenericChipBundle::new(
+                position,
+                PinModelCollection(pin_models),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "TERMINAL"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+}
+
+/// Outputs the oldest typed character on `D*`, `AV` is high while there is one.
+/// A clock edge while `RD` is high moves on to the next character, `CLR` drops all of them.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Keyboard;
+
+impl Device for Keyboard {
+    fn create_bundle(position: Position) -> impl Bundle {
+        let mut pin_models = vec![
+            PinModel::new_input("CLR".into()),
+            PinModel::new_input("C".into()),
+            PinModel::new_input("RD".into()),
+            PinModel::new_output("AV".into()),
+        ];
+        pin_models.extend(numbered_pins("D", DATA_WIDTH, PinModel::new_output));
+
+        (
+            Keyboard,
+            KeyboardBuffer::default(),
+            GenericChipBundle::new(
+                position,
+                PinModelCollection(pin_models),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "KEYBOARD"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Io
+    }
+}
@@ -0,0 +1,250 @@
+//! Devices that route signals by a binary select value.
+//! Multi-bit values are spread over one pin per bit, e.g. `S0`, `S1`, `S2`, where `S0` is the least significant bit.
+
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    position::Position,
+    save_management::format::{DeviceProperties, PropertyValue},
+};
+
+use super::{
+    bit_width::numbered_pins,
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
+
+/// Limits the amount of pins, a select width of 4 already needs 16 data pins.
+const MAX_SELECT_WIDTH: u32 = 4;
+
+/// Amount of select bits of a plexer.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct SelectWidth(pub u32);
+
+impl Default for SelectWidth {
+    fn default() -> Self {
+        Self(2)
+    }
+}
+
+impl SelectWidth {
+    pub fn num_lines(&self) -> u32 {
+        1 << self.0
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = DeviceProperties::default();
+        if let Some(select_width) = device.get::<SelectWidth>() {
+            properties.set("select_width", PropertyValue::Int(select_width.0 as i64));
+        }
+        properties
+    }
+
+    /// Inserts the select width and rebuilds the pins to match it.
+    fn load_properties(
+        device: &mut EntityWorldMut,
+        properties: &DeviceProperties,
+        create_pins: fn(SelectWidth) -> PinModelCollection,
+    ) {
+        let Some(select_width) = properties.get_int("select_width") else {
+            return;
+        };
+
+        let select_width = SelectWidth(select_width.clamp(1, MAX_SELECT_WIDTH as i64) as u32);
+        device.insert((create_pins(select_width), select_width));
+    }
+}
+
+/// Connects one of the data inputs `D*` selected by `S*` to the output `Y`.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Multiplexer;
+
+impl Multiplexer {
+    fn create_pins(select_width: SelectWidth) -> PinModelCollection {
+        let mut pin_models = numbered_pins("S", select_width.0, PinModel::new_input);
+        pin_models.extend(numbered_pins(
+            "D",
+            select_width.num_lines(),
+            PinModel::new_input,
+        ));
+        pin_models.push(PinModel::new_output("Y".into()));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Multiplexer {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            Multiplexer,
+            SelectWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(SelectWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "MUX"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Plexers
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        SelectWidth::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        SelectWidth::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Connects the input `D` to one of the outputs `Y*` selected by `S*`, all other outputs are low.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Demultiplexer;
+
+impl Demultiplexer {
+    fn create_pins(select_width: SelectWidth) -> PinModelCollection {
+        let mut pin_models = numbered_pins("S", select_width.0, PinModel::new_input);
+        pin_models.push(PinModel::new_input("D".into()));
+        pin_models.extend(numbered_pins(
+            "Y",
+            select_width.num_lines(),
+            PinModel::new_output,
+        ));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Demultiplexer {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            Demultiplexer,
+            SelectWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(SelectWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "DEMUX"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Plexers
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        SelectWidth::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        SelectWidth::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Sets the output `Y*` with the index of the address `A*` high and all others low.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Decoder;
+
+impl Decoder {
+    fn create_pins(select_width: SelectWidth) -> PinModelCollection {
+        let mut pin_models = numbered_pins("A", select_width.0, PinModel::new_input);
+        pin_models.extend(numbered_pins(
+            "Y",
+            select_width.num_lines(),
+            PinModel::new_output,
+        ));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Decoder {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            Decoder,
+            SelectWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(SelectWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "DECODER"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Plexers
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        SelectWidth::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        SelectWidth::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Outputs the index of the highest high input `D*` on `A*`.
+/// `V` is high if any input is high, so an index of 0 can be told apart from no input.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct PriorityEncoder;
+
+impl PriorityEncoder {
+    fn create_pins(select_width: SelectWidth) -> PinModelCollection {
+        let mut pin_models = numbered_pins("D", select_width.num_lines(), PinModel::new_input);
+        pin_models.push(PinModel::new_output("V".into()));
+        pin_models.extend(numbered_pins("A", select_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for PriorityEncoder {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            PriorityEncoder,
+            SelectWidth::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(SelectWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "PRIORITY-ENC"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Plexers
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        SelectWidth::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        SelectWidth::load_properties(device, properties, Self::create_pins);
+    }
+}
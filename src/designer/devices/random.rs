@@ -0,0 +1,222 @@
+//! Clocked devices that produce pseudo-random values.
+//! Both start from a seed that is saved with the board, so a board produces the same values every time it is run.
+
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    position::Position,
+    save_management::format::{DeviceProperties, PropertyValue},
+};
+
+use super::{
+    bit_width::{numbered_pins, BitWidth},
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
+
+/// Taps of a maximum length LFSR for every bit width, indexed by the bit width.
+const MAXIMUM_LENGTH_TAPS: [u64; 9] = [
+    0, 0b1, 0b11, 0b110, 0b1100, 0b10100, 0b110000, 0b1100000, 0b10111000,
+];
+
+/// Seed and current state of a pseudo-random device.
+/// Only the seed is saved, the state starts over from the seed when the board is loaded.
+#[derive(Component, Reflect, Clone, Copy)]
+#[reflect(Component)]
+pub struct PseudoRandom {
+    pub seed: u64,
+    pub state: u64,
+}
+
+impl Default for PseudoRandom {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+impl PseudoRandom {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, state: seed }
+    }
+
+    pub fn restart(&mut self) {
+        self.state = self.seed;
+    }
+
+    /// Advances the state of the SplitMix64 generator.
+    pub fn advance(&mut self) {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    }
+
+    /// The SplitMix64 output of the current state.
+    pub fn value(&self) -> u64 {
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = BitWidth::save_properties(device);
+        if let Some(pseudo_random) = device.get::<PseudoRandom>() {
+            properties.set("seed", PropertyValue::Int(pseudo_random.seed as i64));
+        }
+        properties
+    }
+
+    fn load_properties(
+        device: &mut EntityWorldMut,
+        properties: &DeviceProperties,
+        create_pins: fn(BitWidth) -> PinModelCollection,
+    ) {
+        BitWidth::load_properties(device, properties, create_pins);
+
+        let Some(seed) = properties.get_int("seed").map(|seed| seed as u64) else {
+            return;
+        };
+
+        // editing other properties keeps the sequence going
+        if device
+            .get::<PseudoRandom>()
+            .is_none_or(|pseudo_random| pseudo_random.seed != seed)
+        {
+            device.insert(PseudoRandom::new(seed));
+        }
+    }
+}
+
+/// Feedback taps of an LFSR as a bit mask, 0 uses the taps of a maximum length LFSR.
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct LfsrTaps(pub u64);
+
+impl LfsrTaps {
+    /// The taps limited to the bit width.
+    pub fn resolve(&self, bit_width: BitWidth) -> u64 {
+        match self.0 & bit_width.mask() {
+            0 => MAXIMUM_LENGTH_TAPS[bit_width.0 as usize],
+            taps => taps,
+        }
+    }
+}
+
+/// Outputs a new pseudo-random value on a clock edge while `EN` is high, `RST` starts over from the seed.
+/// A tick is a rising edge on `C`, so the value only changes as often as the connected clock ticks
+/// and stays the same while `C` is unconnected.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct RandomSource;
+
+impl RandomSource {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = vec![
+            PinModel::new_input("RST".into()),
+            PinModel::new_input("C".into()),
+            PinModel::new_input("EN".into()),
+        ];
+        pin_models.extend(numbered_pins("Q", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for RandomSource {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            RandomSource,
+            BitWidth::default(),
+            PseudoRandom::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "RANDOM"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Memory
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        PseudoRandom::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        PseudoRandom::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Fibonacci LFSR that shifts towards `Q` of the highest bit on a clock edge while `EN` is high.
+/// `RST` loads the seed. A state of 0 would never change, so seeds without a set bit in the bit
+/// width are replaced with 1.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Lfsr;
+
+impl Lfsr {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = vec![
+            PinModel::new_input("RST".into()),
+            PinModel::new_input("C".into()),
+            PinModel::new_input("EN".into()),
+        ];
+        pin_models.extend(numbered_pins("Q", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Lfsr {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            Lfsr,
+            BitWidth::default(),
+            PseudoRandom::default(),
+            LfsrTaps::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "LFSR"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Memory
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = PseudoRandom::save_properties(device);
+        if let Some(lfsr_taps) = device.get::<LfsrTaps>() {
+            properties.set("taps", PropertyValue::Int(lfsr_taps.0 as i64));
+        }
+        properties
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        PseudoRandom::load_properties(device, properties, Self::create_pins);
+
+        if let Some(taps) = properties.get_int("taps") {
+            device.insert(LfsrTaps(taps as u64));
+        }
+
+        // also applies when only the bit width changed, the properties window shows the new seed
+        let bit_width = device.get::<BitWidth>().copied().unwrap_or_default();
+        if device
+            .get::<PseudoRandom>()
+            .is_some_and(|pseudo_random| pseudo_random.seed & bit_width.mask() == 0)
+        {
+            device.insert(PseudoRandom::new(1));
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Clocked devices that store a multi-bit value.
+//! They trigger on the rising edge of `C`, like the flip-flops.
+
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    position::Position,
+    save_management::format::{DeviceProperties, PropertyValue},
+};
+
+use super::{
+    bit_width::{numbered_pins, BitWidth},
+    device::{Device, DeviceCategory},
+    generic_chip::GenericChipBundle,
+};
+
+/// The value stored in a register, saved with the board so it survives reloading.
+#[derive(Component, Reflect, Clone, Copy, Default)]
+#[reflect(Component)]
+pub struct StoredValue(pub u64);
+
+impl StoredValue {
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        let mut properties = BitWidth::save_properties(device);
+        if let Some(stored_value) = device.get::<StoredValue>() {
+            properties.set("value", PropertyValue::Int(stored_value.0 as i64));
+        }
+        properties
+    }
+
+    fn load_properties(
+        device: &mut EntityWorldMut,
+        properties: &DeviceProperties,
+        create_pins: fn(BitWidth) -> PinModelCollection,
+    ) {
+        BitWidth::load_properties(device, properties, create_pins);
+
+        let Some(value) = properties.get_int("value") else {
+            return;
+        };
+
+        let bit_width = device.get::<BitWidth>().copied().unwrap_or_default();
+        device.insert(StoredValue(value as u64 & bit_width.mask()));
+    }
+}
+
+/// Stores `D*` on a clock edge while `EN` is high, `CLR` resets it to 0 regardless of the clock.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Register;
+
+impl Register {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = vec![
+            PinModel::new_input("CLR".into()),
+            PinModel::new_input("C".into()),
+            PinModel::new_input("EN".into()),
+        ];
+        pin_models.extend(numbered_pins("D", bit_width.0, PinModel::new_input));
+        pin_models.extend(numbered_pins("Q", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Register {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            Register,
+            BitWidth::default(),
+            StoredValue::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "REGISTER"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Memory
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        StoredValue::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        StoredValue::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Counts up on a clock edge while `EN` is high, or down while `DIR` is high as well.
+/// `LD` loads `D*` instead of counting and `CLR` resets to 0 regardless of the clock.
+/// `TC` is high at the terminal count, which is the maximum when counting up and 0 when counting down.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct Counter;
+
+impl Counter {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = vec![
+            PinModel::new_input("CLR".into()),
+            PinModel::new_input("C".into()),
+            PinModel::new_input("EN".into()),
+            PinModel::new_input("LD".into()),
+            PinModel::new_input("DIR".into()),
+        ];
+        pin_models.extend(numbered_pins("D", bit_width.0, PinModel::new_input));
+        pin_models.push(PinModel::new_output("TC".into()));
+        pin_models.extend(numbered_pins("Q", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for Counter {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            Counter,
+            BitWidth::default(),
+            StoredValue::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "COUNTER"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Memory
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        StoredValue::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        StoredValue::load_properties(device, properties, Self::create_pins);
+    }
+}
+
+/// Shifts towards the most significant bit on a clock edge while `EN` is high, `SI` is shifted into `Q0`
+/// and `SO` outputs the most significant bit. `LD` loads `D*` instead of shifting and `CLR` resets to 0
+/// regardless of the clock.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct ShiftRegister;
+
+impl ShiftRegister {
+    fn create_pins(bit_width: BitWidth) -> PinModelCollection {
+        let mut pin_models = vec![
+            PinModel::new_input("CLR".into()),
+            PinModel::new_input("C".into()),
+            PinModel::new_input("EN".into()),
+            PinModel::new_input("LD".into()),
+            PinModel::new_input("SI".into()),
+        ];
+        pin_models.extend(numbered_pins("D", bit_width.0, PinModel::new_input));
+        pin_models.push(PinModel::new_output("SO".into()));
+        pin_models.extend(numbered_pins("Q", bit_width.0, PinModel::new_output));
+
+        PinModelCollection(pin_models)
+    }
+}
+
+impl Device for ShiftRegister {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            ShiftRegister,
+            BitWidth::default(),
+            StoredValue::default(),
+            GenericChipBundle::new(
+                position,
+                Self::create_pins(BitWidth::default()),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "SHIFT-REG"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::Memory
+    }
+
+    fn save_properties(device: &EntityRef) -> DeviceProperties {
+        StoredValue::save_properties(device)
+    }
+
+    fn load_properties(device: &mut EntityWorldMut, properties: &DeviceProperties) {
+        StoredValue::load_properties(device, properties, Self::create_pins);
+    }
+}
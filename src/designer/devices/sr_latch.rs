@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+use crate::designer::{
+    pin::{PinModel, PinModelCollection},
+    position::Position,
+};
+
+use super::{
+    device::{Device, DeviceCategory},
+    flipflop::inverted_output,
+    generic_chip::GenericChipBundle,
+};
+
+/// Level sensitive, unlike the flip-flops it does not wait for a clock edge.
+#[derive(Component, Reflect, Clone)]
+#[reflect(Component)]
+pub struct SRLatch;
+
+impl Device for SRLatch {
+    fn create_bundle(position: Position) -> impl Bundle {
+        (
+            SRLatch,
+            GenericChipBundle::new(
+                position,
+                PinModelCollection(vec![
+                    PinModel::new_input("R".into()),
+                    PinModel::new_input("S".into()),
+                    inverted_output(),
+                    PinModel::new_output("Q".into()),
+                ]),
+                Self::device_id().into(),
+            ),
+        )
+    }
+
+    fn device_id() -> &'static str {
+        "SR-LATCH"
+    }
+
+    fn category() -> DeviceCategory {
+        DeviceCategory::FlipFlops
+    }
+}
@@ -1,6 +1,7 @@
 pub mod bounding_box;
 pub mod copy_paste;
 pub mod cursor;
+pub mod design_check;
 pub mod designer_state;
 pub mod devices;
 pub mod macros;
@@ -18,6 +19,7 @@ use bevy::prelude::*;
 use bevy::transform::TransformSystem;
 use copy_paste::CopyPastePlugin;
 use cursor::CursorPlugin;
+use design_check::DesignCheckPlugin;
 use devices::DevicePlugin;
 use model::{ModelId, ModelRegistry};
 use pin::PinPlugin;
@@ -42,6 +44,7 @@ impl PluginGroup for DesignerPlugins {
             .add(CursorPlugin)
             .add(PinPlugin)
             .add(SaveManagementPlugin)
+            .add(DesignCheckPlugin)
             .add(DesignerPlugin)
     }
 }
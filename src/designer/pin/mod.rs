@@ -98,6 +98,11 @@ impl PinModelCollection {
         self.iter_mut().find(|m| m.uuid.eq(&uuid))
     }
 
+    /// Like indexing by label, but for pins that are optional.
+    pub fn get_by_label(&self, label: &str) -> Option<&PinModel> {
+        self.iter().find(|m| m.label == label)
+    }
+
     pub fn iter_inputs(&self) -> impl Iterator<Item = &PinModel> {
         self.iter().filter(|m| m.pin_type.eq(&PinType::Input))
     }
@@ -114,6 +119,41 @@ impl PinModelCollection {
         self.iter_mut().filter(|m| m.pin_type.eq(&PinType::Input))
     }
 
+    /// Reads the pins `{prefix}0` to `{prefix}{width - 1}` as an unsigned number,
+    /// the pin with index 0 is the least significant bit.
+    /// Returns None if one of the pins has a conflict.
+    pub fn read_bits(&self, prefix: &str, width: u32) -> Option<u64> {
+        let mut value = 0;
+
+        for bit in 0..width {
+            match self[format!("{}{}", prefix, bit).as_str()]
+                .signal_state
+                .get_signal()
+            {
+                Signal::Low => {}
+                Signal::High => value |= 1 << bit,
+                Signal::Conflict => return None,
+            }
+        }
+
+        Some(value)
+    }
+
+    /// Sets the pins `{prefix}0` to `{prefix}{width - 1}` to the bits of the value,
+    /// or all of them to a conflict if there is no value.
+    pub fn write_bits(&mut self, prefix: &str, width: u32, value: Option<u64>) {
+        for bit in 0..width {
+            let signal = match value {
+                Some(value) => Signal::from((value >> bit) & 1 == 1),
+                None => Signal::Conflict,
+            };
+
+            self[format!("{}{}", prefix, bit).as_str()]
+                .signal_state
+                .set_signal(signal);
+        }
+    }
+
     pub fn num_inputs(&self) -> usize {
         self.iter_inputs().count()
     }
@@ -121,10 +161,6 @@ impl PinModelCollection {
     pub fn num_outputs(&self) -> usize {
         self.iter_outputs().count()
     }
-
-    pub fn randomize_pin_uuids(&mut self) {
-        self.iter_mut().for_each(|m| m.uuid = Uuid::new_v4());
-    }
 }
 
 /// Despawns all wires connected to a device when it is despawned.
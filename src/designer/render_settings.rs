@@ -24,11 +24,28 @@ pub struct CircuitBoardRenderingSettings {
     pub binary_switch_extents: Vec2,
     pub binary_display_extents: Vec2,
     pub binary_display_font_size: f32,
+    pub push_button_color: Color,
     pub clock_extents: Vec2,
     pub clock_color: Color,
     pub clock_label_font_size: f32,
     pub clock_pin_radius: f32,
+    pub net_label_extents: Vec2,
+    pub net_label_color: Color,
+    pub net_label_font_size: f32,
+    pub display_color: Color,
+    pub display_label_color: Color,
+    pub light_off_color: Color,
+    pub segment_on_color: Color,
+    pub led_radius: f32,
+    pub annotation_font_size: f32,
+    pub sticky_note_extents: Vec2,
+    pub sticky_note_color: Color,
+    pub frame_title_height: f32,
+    pub frame_color: Color,
+    pub frame_stroke_color: Color,
     pub wire_line_width: f32,
+    pub wire_routing_grid_size: f32,
+    pub wire_routing_clearance: f32,
     pub pin_color: Color,
     pub hovered_pin_color: Color,
     pub selection_box_stroke_color: Color,
@@ -57,11 +74,28 @@ pub fn init_render_settings(app: &mut App) {
         binary_switch_extents: Vec2::new(60.0, 30.0),
         binary_display_extents: Vec2::new(30.0, 30.0),
         binary_display_font_size: 15.0,
+        push_button_color: RED.into(),
         clock_extents: Vec2::new(30.0, 30.0),
         clock_color: WHITE.into(),
         clock_label_font_size: 15.0,
         clock_pin_radius: 7.0,
+        net_label_extents: Vec2::new(70.0, 20.0),
+        net_label_color: WHITE.into(),
+        net_label_font_size: 12.0,
+        display_color: Color::srgb(0.15, 0.15, 0.15),
+        display_label_color: WHITE.into(),
+        light_off_color: Color::srgb(0.3, 0.3, 0.3),
+        segment_on_color: RED.into(),
+        led_radius: 10.0,
+        annotation_font_size: 14.0,
+        sticky_note_extents: Vec2::new(140.0, 90.0),
+        sticky_note_color: Color::srgb(1.0, 0.95, 0.6),
+        frame_title_height: 22.0,
+        frame_color: Color::srgba(1.0, 1.0, 1.0, 0.08),
+        frame_stroke_color: Color::srgb(0.75, 0.78, 0.9),
         wire_line_width: 4.0,
+        wire_routing_grid_size: 10.0,
+        wire_routing_clearance: 10.0,
         pin_color: BLACK.into(),
         hovered_pin_color: Color::srgb(0.4, 0.4, 0.4),
         selection_box_fill_color: Color::srgba(1.0, 1.0, 1.0, 0.1),
@@ -0,0 +1,325 @@
+use std::{
+    collections::HashSet,
+    fs::{self, File, TryLockError},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use uuid::Uuid;
+
+use crate::events::{DiscardRecoveryEvent, RestoreRecoveryEvent};
+
+use super::{
+    encoding::{encode_board, SaveFormat},
+    format::collect_board,
+    get_saves_folder, replace_board,
+    tabs::{new_tab, BoardTabs},
+    ActiveSaveFile, LoadReport,
+};
+
+#[derive(Resource)]
+pub struct AutosaveSettings {
+    /// Time between two writes of the recovery file while the board has unsaved changes.
+    pub interval: Duration,
+}
+
+impl Default for AutosaveSettings {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(60),
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct AutosaveTimer(Timer);
+
+impl FromWorld for AutosaveTimer {
+    fn from_world(world: &mut World) -> Self {
+        let interval = world.resource::<AutosaveSettings>().interval;
+        Self(Timer::new(interval, TimerMode::Repeating))
+    }
+}
+
+/// Open when an earlier session did not exit cleanly and left recovery files behind.
+#[derive(Resource, Default)]
+pub struct RecoveryPrompt {
+    pub open: bool,
+    /// Recovery files of all sessions that are not running anymore.
+    files: Vec<PathBuf>,
+    /// Lock files of those sessions, removed once their recovery files were restored or discarded.
+    lock_files: Vec<PathBuf>,
+}
+
+impl RecoveryPrompt {
+    pub fn board_count(&self) -> usize {
+        self.files.len()
+    }
+}
+
+/// The lock file of this session.
+/// It stays locked while the app is running, so other instances can tell that the session is alive,
+/// and is unlocked by the operating system when the app crashes.
+#[derive(Resource)]
+pub struct SessionLock {
+    pid: u32,
+    file: Option<File>,
+}
+
+/// Lock and recovery files of all running and crashed sessions.
+/// Every file starts with the process id of its session, so several instances can run at once.
+fn get_sessions_folder() -> PathBuf {
+    get_saves_folder().with_file_name("sessions")
+}
+
+fn get_session_lock_file(pid: u32) -> PathBuf {
+    get_sessions_folder().join(format!("{}.lock", pid))
+}
+
+/// The last autosave of a tab, kept until the board is saved or the app exits cleanly.
+/// Named by the id of the tab, so it still belongs to the same tab after other tabs were closed,
+/// and never to a tab of an earlier session that had the same process id.
+fn get_recovery_file(pid: u32, tab_id: Uuid) -> PathBuf {
+    get_sessions_folder().join(format!("{}-{}.logics", pid, tab_id))
+}
+
+/// Reads the process id of a lock or recovery file from its name.
+fn session_pid(path: &Path) -> Option<u32> {
+    let file_stem = path.file_stem()?.to_str()?;
+    file_stem.split('-').next()?.parse().ok()
+}
+
+/// A session is alive as long as its process holds the lock on its lock file.
+fn is_session_alive(pid: u32) -> bool {
+    let Ok(lock_file) = File::open(get_session_lock_file(pid)) else {
+        return false;
+    };
+
+    matches!(lock_file.try_lock(), Err(TryLockError::WouldBlock))
+}
+
+fn remove_file_logged(path: &Path) {
+    if path.exists() {
+        if let Err(error) = fs::remove_file(path) {
+            error!("Failed to remove {}: {}", path.display(), error);
+        }
+    }
+}
+
+/// Removes the recovery file of a tab, because its changes were saved or discarded on purpose.
+pub fn remove_recovery_file(world: &World, tab_id: Uuid) {
+    let Some(session_lock) = world.get_resource::<SessionLock>() else {
+        return;
+    };
+
+    remove_file_logged(&get_recovery_file(session_lock.pid, tab_id));
+}
+
+/// Offers to restore the recovery files of sessions that are not running anymore and locks the
+/// lock file of this session.
+pub fn check_unclean_exit(mut commands: Commands, mut recovery_prompt: ResMut<RecoveryPrompt>) {
+    let pid = std::process::id();
+    let sessions_folder = get_sessions_folder();
+
+    if let Err(error) = fs::create_dir_all(&sessions_folder) {
+        error!("Failed to create {}: {}", sessions_folder.display(), error);
+        return;
+    }
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&sessions_folder)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+
+    // a previous session with the same process id is not running anymore either
+    let mut dead_pids: HashSet<u32> = HashSet::new();
+    for pid in files.iter().filter_map(|file| session_pid(file)) {
+        if !dead_pids.contains(&pid) && !is_session_alive(pid) {
+            dead_pids.insert(pid);
+        }
+    }
+
+    let (mut lock_files, recovery_files): (Vec<PathBuf>, Vec<PathBuf>) = files
+        .into_iter()
+        .filter(|file| session_pid(file).is_some_and(|pid| dead_pids.contains(&pid)))
+        .partition(|file| {
+            file.extension()
+                .is_some_and(|extension| extension == "lock")
+        });
+
+    // the lock file of a dead session with the same process id is reused by this session
+    let session_lock_file = get_session_lock_file(pid);
+    lock_files.retain(|lock_file| *lock_file != session_lock_file);
+
+    if recovery_files.is_empty() {
+        for lock_file in lock_files.iter() {
+            remove_file_logged(lock_file);
+        }
+    } else {
+        *recovery_prompt = RecoveryPrompt {
+            open: true,
+            files: recovery_files,
+            lock_files,
+        };
+    }
+
+    let file = File::create(&session_lock_file).and_then(|file| {
+        file.try_lock().map_err(std::io::Error::from)?;
+        Ok(file)
+    });
+
+    match file {
+        Ok(file) => commands.insert_resource(SessionLock {
+            pid,
+            file: Some(file),
+        }),
+        Err(error) => error!("Failed to lock {}: {}", session_lock_file.display(), error),
+    }
+}
+
+/// Removes the lock and recovery files of this session, because all changes were either saved or
+/// discarded on purpose.
+pub fn clean_up_on_exit(
+    mut exit_events: EventReader<AppExit>,
+    session_lock: Option<ResMut<SessionLock>>,
+    board_tabs: Res<BoardTabs>,
+) {
+    if exit_events.read().next().is_none() {
+        return;
+    }
+
+    let Some(mut session_lock) = session_lock else {
+        return;
+    };
+
+    for tab in board_tabs.tabs.iter() {
+        remove_file_logged(&get_recovery_file(session_lock.pid, tab.id()));
+    }
+
+    // the file has to be closed before it can be removed on every platform
+    session_lock.file.take();
+    remove_file_logged(&get_session_lock_file(session_lock.pid));
+}
+
+/// Uses real time, so autosaving continues while the simulation is paused or slowed down.
+pub fn tick_autosave_timer(mut autosave_timer: ResMut<AutosaveTimer>, time: Res<Time<Real>>) {
+    autosave_timer.0.tick(time.delta());
+}
+
+/// Autosaves when the timer finished and any tab has something to save.
+/// The recovery files are not written while the user still has to decide whether to restore older ones.
+pub fn autosave_due(
+    autosave_timer: Res<AutosaveTimer>,
+    active_save_file: Res<ActiveSaveFile>,
+    board_tabs: Res<BoardTabs>,
+    recovery_prompt: Res<RecoveryPrompt>,
+) -> bool {
+    let modified = active_save_file.modified || board_tabs.tabs.iter().any(|tab| tab.modified);
+
+    autosave_timer.0.just_finished() && modified && !recovery_prompt.open
+}
+
+/// Writes one recovery file per modified tab and removes the ones of unmodified tabs.
+/// Files of closed tabs are removed when the tab is closed.
+pub fn write_recovery_files(world: &mut World) {
+    let Some(pid) = world
+        .get_resource::<SessionLock>()
+        .map(|session_lock| session_lock.pid)
+    else {
+        return;
+    };
+
+    let active_board = world
+        .resource::<ActiveSaveFile>()
+        .modified
+        .then(|| collect_board(world));
+
+    let board_tabs = world.resource::<BoardTabs>();
+    for (index, tab) in board_tabs.tabs.iter().enumerate() {
+        let recovery_file = get_recovery_file(pid, tab.id());
+
+        let board = match index == board_tabs.active {
+            true => active_board.as_ref(),
+            false => tab.modified.then(|| tab.board()),
+        };
+
+        let Some(board) = board else {
+            remove_file_logged(&recovery_file);
+            continue;
+        };
+
+        let result = encode_board(board, SaveFormat::Binary)
+            .and_then(|bytes| fs::write(&recovery_file, bytes).map_err(|error| error.to_string()));
+
+        if let Err(error) = result {
+            error!(
+                "Failed to autosave to {}: {}",
+                recovery_file.display(),
+                error
+            );
+        }
+    }
+}
+
+/// Restores every recovery file as a new, unsaved board in its own tab.
+/// The active tab is reused if it is an empty new file.
+pub fn restore_recovery(world: &mut World) {
+    world.resource_mut::<Events<RestoreRecoveryEvent>>().clear();
+    let recovery_prompt = std::mem::take(&mut *world.resource_mut::<RecoveryPrompt>());
+
+    let mut load_report = LoadReport {
+        path: recovery_prompt.files.first().cloned(),
+        ..default()
+    };
+
+    for (index, recovery_file) in recovery_prompt.files.iter().enumerate() {
+        let active_save_file = world.resource::<ActiveSaveFile>();
+        let reuse_active_tab =
+            index == 0 && active_save_file.path.is_none() && !active_save_file.modified;
+        if !reuse_active_tab {
+            new_tab(world);
+        }
+
+        match replace_board(world, recovery_file) {
+            Ok(repairs) => {
+                let mut active_save_file = world.resource_mut::<ActiveSaveFile>();
+                active_save_file.path = None;
+                active_save_file.modified = true;
+
+                load_report.repairs.extend(repairs);
+            }
+            Err(error) => {
+                error!("Failed to restore {}: {}", recovery_file.display(), error);
+                load_report.error = Some(error);
+            }
+        }
+    }
+
+    *world.resource_mut::<LoadReport>() = load_report;
+
+    // the restored boards are autosaved by this session from now on
+    remove_dead_sessions(&recovery_prompt);
+}
+
+pub fn discard_recovery(
+    mut discard_events: EventReader<DiscardRecoveryEvent>,
+    mut recovery_prompt: ResMut<RecoveryPrompt>,
+) {
+    discard_events.clear();
+    remove_dead_sessions(&std::mem::take(&mut *recovery_prompt));
+}
+
+fn remove_dead_sessions(recovery_prompt: &RecoveryPrompt) {
+    for file in recovery_prompt
+        .files
+        .iter()
+        .chain(recovery_prompt.lock_files.iter())
+    {
+        remove_file_logged(file);
+    }
+}
@@ -0,0 +1,106 @@
+use std::{
+    io::{Read, Write},
+    path::Path,
+};
+
+use bevy::scene::ron;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+
+use super::format::{BoardFile, FORMAT_VERSION};
+
+/// Written at the start of binary save files, followed by the format version as little endian u32.
+const BINARY_MAGIC: &[u8] = b"LOGICS";
+
+/// The file formats a [`BoardFile`] can be written in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SaveFormat {
+    /// Pretty printed RON, easy to read and diff.
+    #[default]
+    Ron,
+    /// JSON for interoperability with other tools.
+    Json,
+    /// Gzip compressed bincode for big boards.
+    Binary,
+}
+
+impl SaveFormat {
+    pub const ALL: [SaveFormat; 3] = [SaveFormat::Ron, SaveFormat::Json, SaveFormat::Binary];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SaveFormat::Ron => "RON",
+            SaveFormat::Json => "JSON",
+            SaveFormat::Binary => "Compressed binary",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            SaveFormat::Ron => "ron",
+            SaveFormat::Json => "json",
+            SaveFormat::Binary => "logics",
+        }
+    }
+
+    /// Chooses the format by the file extension, defaults to RON.
+    pub fn from_path(path: &Path) -> Self {
+        let extension = path.extension().and_then(|extension| extension.to_str());
+
+        Self::ALL
+            .into_iter()
+            .find(|format| Some(format.extension()) == extension)
+            .unwrap_or_default()
+    }
+
+    /// Detects the format by the contents of a file, regardless of its extension.
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(BINARY_MAGIC) {
+            return SaveFormat::Binary;
+        }
+
+        match bytes.iter().find(|byte| !byte.is_ascii_whitespace()) {
+            Some(b'{') => SaveFormat::Json,
+            _ => SaveFormat::Ron,
+        }
+    }
+}
+
+/// Writes the board in the given format.
+pub fn encode_board(board: &BoardFile, format: SaveFormat) -> Result<Vec<u8>, String> {
+    match format {
+        SaveFormat::Ron => ron::ser::to_string_pretty(board, ron::ser::PrettyConfig::default())
+            .map(String::into_bytes)
+            .map_err(|error| error.to_string()),
+        SaveFormat::Json => serde_json::to_vec_pretty(board).map_err(|error| error.to_string()),
+        SaveFormat::Binary => {
+            let payload = bincode::serialize(board).map_err(|error| error.to_string())?;
+
+            let mut bytes = BINARY_MAGIC.to_vec();
+            bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+
+            let mut encoder = GzEncoder::new(bytes, Compression::default());
+            encoder
+                .write_all(&payload)
+                .map_err(|error| error.to_string())?;
+            encoder.finish().map_err(|error| error.to_string())
+        }
+    }
+}
+
+/// Splits a binary save file into its format version and the decompressed payload.
+pub fn read_binary(bytes: &[u8]) -> Result<(u32, Vec<u8>), String> {
+    let header_len = BINARY_MAGIC.len() + 4;
+    if bytes.len() < header_len || !bytes.starts_with(BINARY_MAGIC) {
+        return Err("The file is not a binary save file.".into());
+    }
+
+    let mut version_bytes = [0; 4];
+    version_bytes.copy_from_slice(&bytes[BINARY_MAGIC.len()..header_len]);
+
+    let mut payload = Vec::new();
+    GzDecoder::new(&bytes[header_len..])
+        .read_to_end(&mut payload)
+        .map_err(|error| error.to_string())?;
+
+    Ok((u32::from_le_bytes(version_bytes), payload))
+}
@@ -0,0 +1,606 @@
+//! The save file schema.
+//!
+//! Boards are stored as a [`BoardFile`], which is independent of the internal component types,
+//! so refactoring the designer does not break existing save files.
+//!
+//! ```ron
+//! (
+//!     version: 1,
+//!     devices: [
+//!         (
+//!             id: "d020d8b8-b3cf-4438-8738-cc2ca7f47d21",
+//!             kind: "AND-2",           // device id used in the chip selector
+//!             position: (-285.6, -106.1),
+//!             pins: [
+//!                 (label: "A", id: "6d752830-61bf-41bf-aa3e-fc6efad4d5dc", signal: Low),
+//!             ],
+//!             properties: {},          // device specific settings, e.g. "name" of a NET-LABEL
+//!         ),
+//!     ],
+//!     wire_joints: [
+//!         (id: "4a3e686f-ff24-4ac2-a690-0bdffec8f266", position: (-145.0, -3.7)),
+//!     ],
+//!     wires: [
+//!         (
+//!             id: "8416e558-9b2b-4cea-99ba-1dc41b47d02e",
+//!             nodes: [Pin("6d752830-61bf-41bf-aa3e-fc6efad4d5dc"), Joint("4a3e686f-ff24-4ac2-a690-0bdffec8f266")],
+//!         ),
+//!     ],
+//! )
+//! ```
+//!
+//! Whenever the schema changes in a way old files cant be read with, [`FORMAT_VERSION`] has to be
+//! increased and a migration from the previous version has to be added in [`super::migration`].
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use bevy::{
+    ecs::{query::QueryFilter, system::SystemState},
+    prelude::*,
+};
+use moonshine_core::kind::InstanceRef;
+use moonshine_view::Viewable;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::designer::{
+    devices::device::{DeviceModel, DeviceSerializers, DeviceViewKind},
+    model::ModelId,
+    pin::PinModelCollection,
+    position::Position,
+    selection::Selected,
+    signal::{Signal, SignalState},
+    wire::{
+        wire_joint::{WireJointModel, WireJointModelBundle},
+        WireModel, WireModelBundle, WireNode, WireNodes,
+    },
+};
+
+use super::ActiveSaveFile;
+
+/// Version of the [`BoardFile`] schema that is written when saving.
+pub const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct BoardFile {
+    pub version: u32,
+    pub devices: Vec<DeviceEntry>,
+    pub wire_joints: Vec<WireJointEntry>,
+    pub wires: Vec<WireEntry>,
+}
+
+impl BoardFile {
+    /// Gives every model and pin a new id, so the board can be spawned next to the original.
+    /// Wire connections to models outside of the board are dropped.
+    pub fn with_fresh_ids(&self) -> Self {
+        let mut id_mapping: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut fresh_id = |id: Uuid| *id_mapping.entry(id).or_insert_with(Uuid::new_v4);
+
+        let devices = self
+            .devices
+            .iter()
+            .map(|device| DeviceEntry {
+                id: fresh_id(device.id),
+                pins: device
+                    .pins
+                    .iter()
+                    .map(|pin| PinEntry {
+                        id: fresh_id(pin.id),
+                        ..pin.clone()
+                    })
+                    .collect(),
+                ..device.clone()
+            })
+            .collect();
+
+        let wire_joints = self
+            .wire_joints
+            .iter()
+            .map(|joint| WireJointEntry {
+                id: fresh_id(joint.id),
+                ..joint.clone()
+            })
+            .collect();
+
+        let wires = self
+            .wires
+            .iter()
+            .map(|wire| WireEntry {
+                id: Uuid::new_v4(),
+                nodes: wire
+                    .nodes
+                    .iter()
+                    .filter_map(|node| match node {
+                        WireNodeEntry::Pin(uuid) => {
+                            id_mapping.get(uuid).map(|&id| WireNodeEntry::Pin(id))
+                        }
+                        WireNodeEntry::Joint(uuid) => {
+                            id_mapping.get(uuid).map(|&id| WireNodeEntry::Joint(id))
+                        }
+                    })
+                    .collect::<Vec<WireNodeEntry>>(),
+            })
+            .filter(|wire| wire.nodes.len() >= 2)
+            .collect();
+
+        Self {
+            version: self.version,
+            devices,
+            wire_joints,
+            wires,
+        }
+    }
+
+    /// Center of all device and wire joint positions.
+    pub fn center(&self) -> Vec2 {
+        let positions: Vec<Vec2> = self
+            .devices
+            .iter()
+            .map(|device| device.position.into())
+            .chain(self.wire_joints.iter().map(|joint| joint.position.into()))
+            .collect();
+
+        if positions.is_empty() {
+            return Vec2::ZERO;
+        }
+
+        let min = positions.iter().copied().reduce(Vec2::min).unwrap();
+        let max = positions.iter().copied().reduce(Vec2::max).unwrap();
+        (min + max) / 2.0
+    }
+
+    pub fn translate(&mut self, offset: Vec2) {
+        for device in self.devices.iter_mut() {
+            device.position = (Vec2::from(device.position) + offset).into();
+        }
+
+        for joint in self.wire_joints.iter_mut() {
+            joint.position = (Vec2::from(joint.position) + offset).into();
+        }
+    }
+}
+
+impl Default for BoardFile {
+    fn default() -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            devices: Vec::new(),
+            wire_joints: Vec::new(),
+            wires: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DeviceEntry {
+    pub id: Uuid,
+    pub kind: String,
+    pub position: (f32, f32),
+    pub pins: Vec<PinEntry>,
+    pub properties: DeviceProperties,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PinEntry {
+    pub label: String,
+    pub id: Uuid,
+    pub signal: SignalEntry,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum SignalEntry {
+    Low,
+    High,
+    Conflict,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WireJointEntry {
+    pub id: Uuid,
+    pub position: (f32, f32),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct WireEntry {
+    pub id: Uuid,
+    pub nodes: Vec<WireNodeEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum WireNodeEntry {
+    Pin(Uuid),
+    Joint(Uuid),
+}
+
+/// Device specific settings by name.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DeviceProperties(pub BTreeMap<String, PropertyValue>);
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+#[allow(dead_code)]
+impl DeviceProperties {
+    pub fn set(&mut self, name: &str, value: PropertyValue) {
+        self.0.insert(name.into(), value);
+    }
+
+    pub fn get_bool(&self, name: &str) -> Option<bool> {
+        match self.0.get(name) {
+            Some(PropertyValue::Bool(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_int(&self, name: &str) -> Option<i64> {
+        match self.0.get(name) {
+            Some(PropertyValue::Int(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn get_float(&self, name: &str) -> Option<f64> {
+        match self.0.get(name) {
+            Some(PropertyValue::Float(value)) => Some(*value),
+            Some(PropertyValue::Int(value)) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    pub fn get_text(&self, name: &str) -> Option<&str> {
+        match self.0.get(name) {
+            Some(PropertyValue::Text(value)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl From<&Signal> for SignalEntry {
+    fn from(signal: &Signal) -> Self {
+        match signal {
+            Signal::Low => SignalEntry::Low,
+            Signal::High => SignalEntry::High,
+            Signal::Conflict => SignalEntry::Conflict,
+        }
+    }
+}
+
+impl From<SignalEntry> for Signal {
+    fn from(signal: SignalEntry) -> Self {
+        match signal {
+            SignalEntry::Low => Signal::Low,
+            SignalEntry::High => Signal::High,
+            SignalEntry::Conflict => Signal::Conflict,
+        }
+    }
+}
+
+impl From<&WireNode> for WireNodeEntry {
+    fn from(wire_node: &WireNode) -> Self {
+        match wire_node {
+            WireNode::Pin(uuid) => WireNodeEntry::Pin(*uuid),
+            WireNode::Joint(uuid) => WireNodeEntry::Joint(*uuid),
+        }
+    }
+}
+
+impl From<&WireNodeEntry> for WireNode {
+    fn from(wire_node: &WireNodeEntry) -> Self {
+        match wire_node {
+            WireNodeEntry::Pin(uuid) => WireNode::Pin(*uuid),
+            WireNodeEntry::Joint(uuid) => WireNode::Joint(*uuid),
+        }
+    }
+}
+
+/// Collects all models of the board into a [`BoardFile`].
+pub fn collect_board(world: &mut World) -> BoardFile {
+    collect_models::<()>(world)
+}
+
+/// Collects the selected models into a [`BoardFile`].
+pub fn collect_selection(world: &mut World) -> BoardFile {
+    collect_models::<With<Selected>>(world)
+}
+
+fn collect_models<F: QueryFilter>(world: &mut World) -> BoardFile {
+    let mut board = BoardFile::default();
+
+    let device_entities: Vec<Entity> = world
+        .query_filtered::<Entity, (With<DeviceModel>, F)>()
+        .iter(world)
+        .collect();
+
+    let device_serializers = world.resource::<DeviceSerializers>();
+    board.devices = device_entities
+        .into_iter()
+        .filter_map(|device_entity| collect_device(world, device_entity, device_serializers))
+        .collect();
+
+    board.wire_joints = world
+        .query_filtered::<(&ModelId, &Position), (With<WireJointModel>, F)>()
+        .iter(world)
+        .map(|(model_id, position)| WireJointEntry {
+            id: model_id.0,
+            position: position.0.into(),
+        })
+        .collect();
+
+    board.wires = world
+        .query_filtered::<(&ModelId, &WireNodes), (With<WireModel>, F)>()
+        .iter(world)
+        .map(|(model_id, wire_nodes)| WireEntry {
+            id: model_id.0,
+            nodes: wire_nodes.0.iter().map(WireNodeEntry::from).collect(),
+        })
+        .collect();
+
+    board
+}
+
+fn collect_device(
+    world: &World,
+    device_entity: Entity,
+    device_serializers: &DeviceSerializers,
+) -> Option<DeviceEntry> {
+    let device = world.entity(device_entity);
+
+    let (Some(model_id), Some(position), Some(pin_model_collection)) = (
+        device.get::<ModelId>(),
+        device.get::<Position>(),
+        device.get::<PinModelCollection>(),
+    ) else {
+        return None;
+    };
+
+    let Some(kind) = device_serializers.device_id(&device) else {
+        warn!("Skipped saving unknown device {}", device_entity);
+        return None;
+    };
+
+    let properties = device_serializers
+        .0
+        .get(&kind)
+        .map(|serializer| (serializer.save_properties)(&device))
+        .unwrap_or_default();
+
+    Some(DeviceEntry {
+        id: model_id.0,
+        kind,
+        position: position.0.into(),
+        pins: pin_model_collection
+            .iter()
+            .map(|pin_model| PinEntry {
+                label: pin_model.label.clone(),
+                id: pin_model.uuid,
+                signal: pin_model.signal_state.get_signal().into(),
+            })
+            .collect(),
+        properties,
+    })
+}
+
+/// Reads the device id and the properties of a device.
+pub fn read_device_properties(
+    world: &World,
+    device_entity: Entity,
+) -> Option<(String, DeviceProperties)> {
+    let device_serializers = world.resource::<DeviceSerializers>();
+    let device = world.get_entity(device_entity).ok()?;
+    let kind = device_serializers.device_id(&device)?;
+    let properties = (device_serializers.0.get(&kind)?.save_properties)(&device);
+
+    Some((kind, properties))
+}
+
+/// Applies changed properties to a device in place and rebuilds its view.
+/// Pins that still exist afterwards keep their ids and signals, so the connected wires stay connected
+/// and stored state like the output of a flip-flop is kept.
+/// Wires to pins that do not exist anymore only lose that end, no wires or joints are despawned.
+/// The board is marked as modified, because property changes are not found by change detection.
+pub fn apply_device_properties(
+    world: &mut World,
+    device_entity: Entity,
+    properties: &DeviceProperties,
+) -> Option<()> {
+    let old_pins: Vec<(String, Uuid, SignalState)> = world
+        .get::<PinModelCollection>(device_entity)?
+        .iter()
+        .map(|pin_model| {
+            (
+                pin_model.label.clone(),
+                pin_model.uuid,
+                pin_model.signal_state.clone(),
+            )
+        })
+        .collect();
+
+    world.resource_scope(|world, device_serializers: Mut<DeviceSerializers>| {
+        let device = world.get_entity(device_entity).ok()?;
+        let kind = device_serializers.device_id(&device)?;
+        let serializer = device_serializers.0.get(&kind)?;
+
+        (serializer.load_properties)(&mut world.entity_mut(device_entity), properties);
+        Some(())
+    })?;
+
+    // the pins are created again if the properties change them, so the old ids are restored by label
+    let mut pin_model_collection = world.get_mut::<PinModelCollection>(device_entity)?;
+    for pin_model in pin_model_collection.iter_mut() {
+        if let Some((_, uuid, signal_state)) = old_pins
+            .iter()
+            .find(|(label, _, _)| *label == pin_model.label)
+        {
+            pin_model.uuid = *uuid;
+            pin_model.signal_state = signal_state.clone();
+        }
+    }
+
+    let new_pin_ids: HashSet<Uuid> = pin_model_collection.iter().map(|pin| pin.uuid).collect();
+    let removed_pin_ids: HashSet<Uuid> = old_pins
+        .iter()
+        .map(|(_, uuid, _)| *uuid)
+        .filter(|uuid| !new_pin_ids.contains(uuid))
+        .collect();
+
+    if !removed_pin_ids.is_empty() {
+        for mut wire_nodes in world
+            .query_filtered::<&mut WireNodes, With<WireModel>>()
+            .iter_mut(world)
+        {
+            let is_connected = wire_nodes.0.iter().any(|wire_node| {
+                matches!(wire_node, WireNode::Pin(uuid) if removed_pin_ids.contains(uuid))
+            });
+
+            if is_connected {
+                wire_nodes.0.retain(|wire_node| {
+                    !matches!(wire_node, WireNode::Pin(uuid) if removed_pin_ids.contains(uuid))
+                });
+            }
+        }
+    }
+
+    rebuild_device_view(world, device_entity);
+
+    if let Some(mut active_save_file) = world.get_resource_mut::<ActiveSaveFile>() {
+        active_save_file.modified = true;
+    }
+
+    Some(())
+}
+
+/// Despawns the view of a device, a new one is built from the current model.
+fn rebuild_device_view(world: &mut World, device_entity: Entity) {
+    let Some(view_entity) = world
+        .get::<Viewable<DeviceViewKind>>(device_entity)
+        .map(|viewable| viewable.view().entity())
+    else {
+        return;
+    };
+
+    // only the view entity itself is despawned when rebuilding
+    world.entity_mut(view_entity).despawn_descendants();
+
+    let mut system_state: SystemState<(Query<InstanceRef<Viewable<DeviceViewKind>>>, Commands)> =
+        SystemState::new(world);
+    let (q_viewables, mut commands) = system_state.get_mut(world);
+    if let Ok(viewable) = q_viewables.get(device_entity) {
+        moonshine_view::rebuild(viewable, &mut commands);
+    }
+    system_state.apply(world);
+}
+
+pub struct SpawnedBoard {
+    pub entities: Vec<Entity>,
+    /// Readable description of everything that could not be spawned.
+    pub repairs: Vec<String>,
+}
+
+/// Spawns all models of the [`BoardFile`].
+pub fn spawn_board(world: &mut World, board: &BoardFile) -> SpawnedBoard {
+    let mut entities: Vec<Entity> = Vec::new();
+    let mut repairs: Vec<String> = Vec::new();
+
+    world.resource_scope(|world, device_serializers: Mut<DeviceSerializers>| {
+        for device_entry in board.devices.iter() {
+            let Some(serializer) = device_serializers.0.get(&device_entry.kind) else {
+                repairs.push(format!("Removed unknown device \"{}\".", device_entry.kind));
+                continue;
+            };
+
+            let device_entity = (serializer.spawn)(world, Position(device_entry.position.into()));
+            entities.push(device_entity);
+            let mut device = world.entity_mut(device_entity);
+
+            set_model_id(&mut device, device_entry.id);
+            (serializer.load_properties)(&mut device, &device_entry.properties);
+
+            let Some(mut pin_model_collection) = device.get_mut::<PinModelCollection>() else {
+                continue;
+            };
+
+            for pin_entry in device_entry.pins.iter() {
+                match pin_model_collection
+                    .iter_mut()
+                    .find(|pin_model| pin_model.label == pin_entry.label)
+                {
+                    Some(pin_model) => {
+                        pin_model.uuid = pin_entry.id;
+                        pin_model.signal_state = SignalState::new(pin_entry.signal.into());
+                    }
+                    None => repairs.push(format!(
+                        "Removed unknown pin {} of {}.",
+                        pin_entry.label, device_entry.kind
+                    )),
+                }
+            }
+        }
+    });
+
+    for joint_entry in board.wire_joints.iter() {
+        let mut joint = world.spawn(WireJointModelBundle::new(Position(
+            joint_entry.position.into(),
+        )));
+        set_model_id(&mut joint, joint_entry.id);
+        entities.push(joint.id());
+    }
+
+    for wire_entry in board.wires.iter() {
+        let mut wire = world.spawn(WireModelBundle::new(
+            wire_entry.nodes.iter().map(WireNode::from).collect(),
+        ));
+        set_model_id(&mut wire, wire_entry.id);
+        entities.push(wire.id());
+    }
+
+    world.flush();
+
+    SpawnedBoard { entities, repairs }
+}
+
+/// Replaces the id of a freshly spawned model with the stored one.
+/// The component is removed first, so the hooks of [`ModelId`] update the registry.
+fn set_model_id(model: &mut EntityWorldMut, uuid: Uuid) {
+    model.remove::<ModelId>();
+    model.insert(ModelId(uuid));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        designer::{
+            devices::{annotation::TextAnnotation, register_devices},
+            model::ModelRegistry,
+        },
+        events::SpawnDeviceEvent,
+    };
+
+    #[test]
+    fn editing_properties_marks_the_board_modified() {
+        let mut app = App::new();
+        app.add_event::<SpawnDeviceEvent>()
+            .init_resource::<ModelRegistry>()
+            .init_resource::<ActiveSaveFile>();
+        register_devices(&mut app);
+
+        let world = app.world_mut();
+        let spawn = world.resource::<DeviceSerializers>().0["TEXT"].spawn;
+        let device_entity = spawn(world, Position(Vec2::ZERO));
+
+        let (_, mut properties) = read_device_properties(world, device_entity).unwrap();
+        properties.set("text", PropertyValue::Text("Decoder".into()));
+
+        assert!(apply_device_properties(world, device_entity, &properties).is_some());
+        assert_eq!(
+            world.get::<TextAnnotation>(device_entity).unwrap().text,
+            "Decoder"
+        );
+        assert!(world.resource::<ActiveSaveFile>().modified);
+    }
+}
@@ -0,0 +1,339 @@
+//! Component libraries are files with reusable parts that can be shared between boards.
+//!
+//! A part is a group of devices, wire joints and wires stored as a [`BoardFile`].
+//! Imported libraries are listed in the chip selector, grouped by library.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::{prelude::*, scene::ron, tasks::AsyncComputeTaskPool};
+use crossbeam_channel::bounded;
+use rfd::AsyncFileDialog;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    designer::copy_paste::{attach_to_cursor, paste_board},
+    events::{ExportLibraryRequestEvent, ImportLibraryRequestEvent, SpawnLibraryPartEvent},
+};
+
+use super::{
+    format::{collect_selection, BoardFile, FORMAT_VERSION},
+    get_saves_folder, AsyncReceiver, AsyncSender,
+};
+
+pub const LIBRARY_EXTENSION: &str = "logicslib";
+
+pub struct LibraryPlugin;
+
+impl Plugin for LibraryPlugin {
+    fn build(&self, app: &mut App) {
+        let (itx, irx) = bounded::<ImportLibraryPick>(1);
+        app.insert_resource(AsyncSender(itx));
+        app.insert_resource(AsyncReceiver(irx));
+
+        let (etx, erx) = bounded::<ExportLibraryPick>(1);
+        app.insert_resource(AsyncSender(etx));
+        app.insert_resource(AsyncReceiver(erx));
+
+        app.init_resource::<ComponentLibraries>()
+            .init_resource::<ExportLibraryPrompt>()
+            .add_systems(Startup, load_component_libraries)
+            .add_systems(
+                Update,
+                (
+                    handle_import_library_request.run_if(on_event::<ImportLibraryRequestEvent>),
+                    handle_export_library_request.run_if(on_event::<ExportLibraryRequestEvent>),
+                    handle_import_library_picked_result,
+                ),
+            )
+            .add_systems(
+                First,
+                (
+                    export_library,
+                    spawn_library_part.run_if(on_event::<SpawnLibraryPartEvent>),
+                ),
+            );
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LibraryFile {
+    pub version: u32,
+    pub name: String,
+    pub parts: Vec<LibraryPart>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LibraryPart {
+    pub name: String,
+    pub board: BoardFile,
+}
+
+/// An imported library and the file it was read from.
+pub struct ComponentLibrary {
+    pub path: PathBuf,
+    pub file: LibraryFile,
+}
+
+/// All imported libraries in import order.
+/// The paths are persisted next to the saves folder, so the libraries are imported again on the next launch.
+#[derive(Resource, Default)]
+pub struct ComponentLibraries {
+    pub libraries: Vec<ComponentLibrary>,
+}
+
+impl ComponentLibraries {
+    /// Adds the library or replaces it if it was imported before.
+    pub fn insert(&mut self, path: PathBuf, file: LibraryFile) {
+        match self
+            .libraries
+            .iter_mut()
+            .find(|library| library.path == path)
+        {
+            Some(library) => library.file = file,
+            None => self.libraries.push(ComponentLibrary { path, file }),
+        }
+    }
+
+    pub fn get_part(&self, path: &Path, part_name: &str) -> Option<&LibraryPart> {
+        self.libraries
+            .iter()
+            .find(|library| library.path == path)?
+            .file
+            .parts
+            .iter()
+            .find(|part| part.name == part_name)
+    }
+
+    fn store(&self) {
+        let libraries_file = get_libraries_file();
+        let paths: Vec<&PathBuf> = self.libraries.iter().map(|library| &library.path).collect();
+
+        let result = ron::ser::to_string_pretty(&paths, ron::ser::PrettyConfig::default())
+            .map_err(|error| error.to_string())
+            .and_then(|contents| {
+                fs::write(&libraries_file, contents).map_err(|error| error.to_string())
+            });
+
+        if let Err(error) = result {
+            error!("Failed to store {}: {}", libraries_file.display(), error);
+        }
+    }
+}
+
+/// Asks for the name of the part before the selection is exported.
+#[derive(Resource)]
+pub struct ExportLibraryPrompt {
+    pub open: bool,
+    pub part_name: String,
+}
+
+impl Default for ExportLibraryPrompt {
+    fn default() -> Self {
+        Self {
+            open: false,
+            part_name: "Part".into(),
+        }
+    }
+}
+
+struct ImportLibraryPick(PathBuf);
+struct ExportLibraryPick {
+    path: PathBuf,
+    part_name: String,
+}
+
+fn get_libraries_file() -> PathBuf {
+    get_saves_folder().with_file_name("libraries.ron")
+}
+
+pub fn read_library(path: &Path) -> Result<LibraryFile, String> {
+    let contents = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    let library: LibraryFile = ron::from_str(&contents).map_err(|error| error.to_string())?;
+
+    if library.version != FORMAT_VERSION {
+        return Err(format!(
+            "The library was saved with format version {}, but only version {} is supported.",
+            library.version, FORMAT_VERSION
+        ));
+    }
+
+    Ok(library)
+}
+
+fn write_library(path: &Path, library: &LibraryFile) -> Result<(), String> {
+    ron::ser::to_string_pretty(library, ron::ser::PrettyConfig::default())
+        .map_err(|error| error.to_string())
+        .and_then(|contents| fs::write(path, contents).map_err(|error| error.to_string()))
+}
+
+/// Imports the libraries of the last session, libraries that cant be read anymore are dropped.
+pub fn load_component_libraries(mut component_libraries: ResMut<ComponentLibraries>) {
+    let Ok(contents) = fs::read_to_string(get_libraries_file()) else {
+        return;
+    };
+
+    let paths = match ron::from_str::<Vec<PathBuf>>(&contents) {
+        Ok(paths) => paths,
+        Err(error) => {
+            warn!("Ignoring broken library list: {}", error);
+            return;
+        }
+    };
+
+    for path in paths {
+        match read_library(&path) {
+            Ok(library) => component_libraries.insert(path, library),
+            Err(error) => warn!("Failed to import {}: {}", path.display(), error),
+        }
+    }
+}
+
+fn handle_import_library_request(sender: Res<AsyncSender<ImportLibraryPick>>) {
+    let sender = sender.0.clone();
+
+    AsyncComputeTaskPool::get()
+        .spawn(async move {
+            let result = AsyncFileDialog::new()
+                .add_filter("component library", &[LIBRARY_EXTENSION])
+                .set_directory(get_saves_folder())
+                .pick_file()
+                .await;
+
+            if let Some(file_handle) = result {
+                sender
+                    .send(ImportLibraryPick(file_handle.path().to_path_buf()))
+                    .unwrap();
+            }
+        })
+        .detach();
+}
+
+fn handle_import_library_picked_result(
+    receiver: Res<AsyncReceiver<ImportLibraryPick>>,
+    mut component_libraries: ResMut<ComponentLibraries>,
+) {
+    for ImportLibraryPick(path) in receiver.try_iter() {
+        match read_library(&path) {
+            Ok(library) => {
+                component_libraries.insert(path, library);
+                component_libraries.store();
+            }
+            Err(error) => error!("Failed to import {}: {}", path.display(), error),
+        }
+    }
+}
+
+fn handle_export_library_request(
+    mut export_events: EventReader<ExportLibraryRequestEvent>,
+    sender: Res<AsyncSender<ExportLibraryPick>>,
+) {
+    let Some(export_ev) = export_events.read().last() else {
+        return;
+    };
+
+    let sender = sender.0.clone();
+    let part_name = export_ev.part_name.clone();
+
+    AsyncComputeTaskPool::get()
+        .spawn(async move {
+            let result = AsyncFileDialog::new()
+                .add_filter("component library", &[LIBRARY_EXTENSION])
+                .set_directory(get_saves_folder())
+                .set_file_name(format!("{}.{}", part_name, LIBRARY_EXTENSION))
+                .save_file()
+                .await;
+
+            if let Some(file_handle) = result {
+                sender
+                    .send(ExportLibraryPick {
+                        path: file_handle.path().to_path_buf(),
+                        part_name,
+                    })
+                    .unwrap();
+            }
+        })
+        .detach();
+}
+
+/// Writes the selection as a part into the picked library file.
+/// Existing libraries keep their other parts, a part with the same name is replaced.
+pub fn export_library(world: &mut World) {
+    let picks: Vec<ExportLibraryPick> = world
+        .resource::<AsyncReceiver<ExportLibraryPick>>()
+        .try_iter()
+        .collect();
+
+    for ExportLibraryPick { path, part_name } in picks {
+        let board = collect_selection(world);
+        if board.devices.is_empty() && board.wire_joints.is_empty() {
+            warn!("Nothing selected to export");
+            continue;
+        }
+
+        let mut library = match path.exists() {
+            true => match read_library(&path) {
+                Ok(library) => library,
+                Err(error) => {
+                    error!("Failed to export to {}: {}", path.display(), error);
+                    continue;
+                }
+            },
+            false => LibraryFile {
+                version: FORMAT_VERSION,
+                name: path
+                    .file_stem()
+                    .and_then(|file_stem| file_stem.to_str())
+                    .unwrap_or("Library")
+                    .to_string(),
+                parts: Vec::new(),
+            },
+        };
+
+        library.parts.retain(|part| part.name != part_name);
+        library.parts.push(LibraryPart {
+            name: part_name,
+            board,
+        });
+
+        if let Err(error) = write_library(&path, &library) {
+            error!("Failed to export to {}: {}", path.display(), error);
+            continue;
+        }
+
+        // exported libraries are available in the chip selector right away
+        let mut component_libraries = world.resource_mut::<ComponentLibraries>();
+        component_libraries.insert(path, library);
+        component_libraries.store();
+    }
+}
+
+/// Spawns a copy of a library part and selects it, optionally dragging it with the cursor.
+pub fn spawn_library_part(world: &mut World) {
+    let spawn_events: Vec<SpawnLibraryPartEvent> = world
+        .resource_mut::<Events<SpawnLibraryPartEvent>>()
+        .drain()
+        .collect();
+
+    for spawn_ev in spawn_events {
+        let Some(part) = world
+            .resource::<ComponentLibraries>()
+            .get_part(&spawn_ev.library, &spawn_ev.part)
+            .cloned()
+        else {
+            warn!("Unknown library part {}", spawn_ev.part);
+            continue;
+        };
+
+        let mut board = part.board.with_fresh_ids();
+        board.translate(spawn_ev.position.0 - board.center());
+
+        let spawned_entities = paste_board(world, &board);
+
+        if spawn_ev.init_drag {
+            attach_to_cursor(world, &spawned_entities, spawn_ev.position.0);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! Loading the contents of memory devices from files.
+//!
+//! Supported are Intel HEX files, text files with whitespace separated hex words and raw binary
+//! files with one byte per word.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::{prelude::*, tasks::AsyncComputeTaskPool};
+use crossbeam_channel::bounded;
+use rfd::AsyncFileDialog;
+use uuid::Uuid;
+
+use crate::{
+    designer::{
+        devices::{bit_width::BitWidth, memory::MemoryContents},
+        model::{ModelId, ModelRegistry},
+    },
+    events::LoadMemoryImageRequestEvent,
+};
+
+use super::{get_saves_folder, ActiveSaveFile, AsyncReceiver, AsyncSender};
+
+/// Guards against records at addresses far beyond any memory device, e.g. flash addresses of microcontrollers.
+const MAX_IMAGE_WORDS: usize = 1 << 16;
+
+pub struct MemoryImagePlugin;
+
+impl Plugin for MemoryImagePlugin {
+    fn build(&self, app: &mut App) {
+        let (tx, rx) = bounded::<MemoryImagePick>(1);
+        app.insert_resource(AsyncSender(tx));
+        app.insert_resource(AsyncReceiver(rx));
+
+        app.add_systems(
+            Update,
+            (
+                handle_load_memory_image_request.run_if(on_event::<LoadMemoryImageRequestEvent>),
+                handle_memory_image_picked_result,
+            ),
+        );
+    }
+}
+
+struct MemoryImagePick {
+    /// The device is looked up by id, it might have been respawned while the dialog was open.
+    device_id: Uuid,
+    path: PathBuf,
+}
+
+fn handle_load_memory_image_request(
+    mut load_events: EventReader<LoadMemoryImageRequestEvent>,
+    q_model_ids: Query<&ModelId, With<MemoryContents>>,
+    sender: Res<AsyncSender<MemoryImagePick>>,
+) {
+    let Some(load_ev) = load_events.read().last() else {
+        return;
+    };
+
+    let Ok(model_id) = q_model_ids.get(load_ev.device) else {
+        return;
+    };
+
+    let sender = sender.0.clone();
+    let device_id = model_id.0;
+
+    AsyncComputeTaskPool::get()
+        .spawn(async move {
+            let result = AsyncFileDialog::new()
+                .add_filter("memory image", &["hex", "txt", "bin"])
+                .set_directory(get_saves_folder())
+                .pick_file()
+                .await;
+
+            if let Some(file_handle) = result {
+                sender
+                    .send(MemoryImagePick {
+                        device_id,
+                        path: file_handle.path().to_path_buf(),
+                    })
+                    .unwrap();
+            }
+        })
+        .detach();
+}
+
+fn handle_memory_image_picked_result(
+    receiver: Res<AsyncReceiver<MemoryImagePick>>,
+    model_registry: Res<ModelRegistry>,
+    mut q_memories: Query<(&mut MemoryContents, &BitWidth)>,
+    mut active_save_file: ResMut<ActiveSaveFile>,
+) {
+    for pick in receiver.try_iter() {
+        let Some((mut memory_contents, bit_width)) = model_registry
+            .try_get_model_entity(&pick.device_id)
+            .and_then(|device_entity| q_memories.get_mut(device_entity).ok())
+        else {
+            warn!("The memory device was removed before its contents were loaded.");
+            continue;
+        };
+
+        match read_memory_image(&pick.path) {
+            Ok(words) => {
+                let dropped_words = memory_contents.load(&words, *bit_width);
+                if dropped_words > 0 {
+                    warn!(
+                        "{} words of {} do not fit into the memory.",
+                        dropped_words,
+                        pick.path.display()
+                    );
+                }
+                active_save_file.modified = true;
+            }
+            Err(error) => error!("Failed to load {}: {}", pick.path.display(), error),
+        }
+    }
+}
+
+/// Reads the words of a memory image, the format is chosen by the extension and contents.
+pub fn read_memory_image(path: &Path) -> Result<Vec<u64>, String> {
+    let bytes = fs::read(path).map_err(|error| error.to_string())?;
+
+    if path.extension().is_some_and(|extension| extension == "bin") {
+        return Ok(bytes.into_iter().map(u64::from).collect());
+    }
+
+    let text = String::from_utf8(bytes).map_err(|error| error.to_string())?;
+    match text.trim_start().starts_with(':') {
+        true => parse_intel_hex(&text),
+        false => parse_hex_words(&text),
+    }
+}
+
+/// Parses whitespace separated hex words, e.g. `3f 00 a2`.
+/// Text after `#` is a comment until the end of the line.
+pub fn parse_hex_words(text: &str) -> Result<Vec<u64>, String> {
+    text.lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(str::split_whitespace)
+        .map(|word| {
+            let digits = word.trim_start_matches("0x");
+            u64::from_str_radix(digits, 16).map_err(|error| format!("\"{}\": {}", word, error))
+        })
+        .collect()
+}
+
+/// Parses the data records of an Intel HEX file into one word per byte.
+/// Gaps between records are filled with zeros.
+pub fn parse_intel_hex(text: &str) -> Result<Vec<u64>, String> {
+    let mut words: Vec<u64> = Vec::new();
+    let mut base_address: usize = 0;
+
+    for (line_index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let error = |message: &str| format!("line {}: {}", line_index + 1, message);
+
+        let record = line
+            .strip_prefix(':')
+            .ok_or_else(|| error("missing ':' at the start of the record"))?;
+        let bytes = (0..record.len())
+            .step_by(2)
+            .map(|i| {
+                record
+                    .get(i..i + 2)
+                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                    .ok_or_else(|| error("invalid hex digits"))
+            })
+            .collect::<Result<Vec<u8>, String>>()?;
+
+        if bytes.len() < 5 || bytes.len() != bytes[0] as usize + 5 {
+            return Err(error("wrong record length"));
+        }
+        if bytes.iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte)) != 0 {
+            return Err(error("wrong checksum"));
+        }
+
+        let data = &bytes[4..bytes.len() - 1];
+        let address = u16::from_be_bytes([bytes[1], bytes[2]]) as usize;
+
+        match bytes[3] {
+            // data
+            0x00 => {
+                let start = base_address + address;
+                if start + data.len() > MAX_IMAGE_WORDS {
+                    return Err(error("address is outside of the supported range"));
+                }
+                if words.len() < start + data.len() {
+                    words.resize(start + data.len(), 0);
+                }
+                for (offset, byte) in data.iter().enumerate() {
+                    words[start + offset] = *byte as u64;
+                }
+            }
+            // end of file
+            0x01 => break,
+            // extended segment address
+            0x02 if data.len() == 2 => {
+                base_address = (u16::from_be_bytes([data[0], data[1]]) as usize) << 4;
+            }
+            // extended linear address
+            0x04 if data.len() == 2 => {
+                base_address = (u16::from_be_bytes([data[0], data[1]]) as usize) << 16;
+            }
+            // start addresses only matter for processors
+            0x03 | 0x05 => {}
+            record_type => return Err(error(&format!("unknown record type {}", record_type))),
+        }
+    }
+
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_hex_words_with_comments() {
+        let text = "3f 0x00 A2 # first row\n\n# only a comment\n7\n";
+
+        assert_eq!(parse_hex_words(text), Ok(vec![0x3f, 0x00, 0xa2, 0x07]));
+    }
+
+    #[test]
+    fn rejects_invalid_hex_words() {
+        assert!(parse_hex_words("12 zz").is_err());
+    }
+
+    #[test]
+    fn parses_intel_hex_data_records() {
+        let text = ":0300300002337A1E\n:00000001FF\n:0100000055AA\n";
+
+        let mut expected = vec![0; 0x33];
+        expected[0x30..].copy_from_slice(&[0x02, 0x33, 0x7a]);
+
+        // records after the end of file record are ignored
+        assert_eq!(parse_intel_hex(text), Ok(expected));
+    }
+
+    #[test]
+    fn rejects_intel_hex_with_wrong_checksum() {
+        let error = parse_intel_hex(":0300300002337A1F").unwrap_err();
+
+        assert!(error.contains("line 1"));
+        assert!(error.contains("checksum"));
+    }
+
+    #[test]
+    fn applies_extended_linear_address() {
+        // 0x0001 moves the following records to 0x10000, which is outside of the supported range
+        let outside = ":020000040001F9\n:0100000055AA\n";
+        assert!(parse_intel_hex(outside)
+            .unwrap_err()
+            .contains("outside of the supported range"));
+
+        // 0x0000 moves them back to the start
+        let inside = ":020000040001F9\n:020000040000FA\n:0100010055A9\n";
+        assert_eq!(parse_intel_hex(inside), Ok(vec![0x00, 0x55]));
+    }
+}
@@ -0,0 +1,356 @@
+//! Reads save files of every known format version and upgrades them to the current [`BoardFile`].
+//!
+//! Version 0 are the files written before the format was versioned.
+//! These are bevy scenes keyed by the full type paths of the components at that time
+//! and only exist as RON. JSON and binary files start at version 1.
+
+use std::{collections::BTreeMap, fmt};
+
+use bevy::scene::ron;
+use serde::{
+    de::{IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use uuid::Uuid;
+
+use super::encoding::{read_binary, SaveFormat};
+use super::format::{
+    BoardFile, DeviceEntry, DeviceProperties, PinEntry, PropertyValue, SignalEntry, WireEntry,
+    WireJointEntry, WireNodeEntry, FORMAT_VERSION,
+};
+
+/// Only reads the version of a save file and ignores everything else.
+#[derive(Deserialize)]
+struct VersionHeader {
+    #[serde(default)]
+    version: u32,
+}
+
+/// Reads a save file of any known format and version and migrates it to the current format.
+pub fn read_board(bytes: &[u8]) -> Result<BoardFile, String> {
+    match SaveFormat::detect(bytes) {
+        SaveFormat::Ron => {
+            let contents = std::str::from_utf8(bytes).map_err(|error| error.to_string())?;
+            let header: VersionHeader =
+                ron::from_str(contents).map_err(|error| error.to_string())?;
+
+            match header.version {
+                0 => read_v0(contents).map(migrate_v0),
+                FORMAT_VERSION => ron::from_str(contents).map_err(|error| error.to_string()),
+                version => Err(unsupported_version(version)),
+            }
+        }
+        SaveFormat::Json => {
+            let header: VersionHeader =
+                serde_json::from_slice(bytes).map_err(|error| error.to_string())?;
+
+            match header.version {
+                FORMAT_VERSION => serde_json::from_slice(bytes).map_err(|error| error.to_string()),
+                version => Err(unsupported_version(version)),
+            }
+        }
+        SaveFormat::Binary => {
+            let (version, payload) = read_binary(bytes)?;
+
+            match version {
+                FORMAT_VERSION => bincode::deserialize(&payload).map_err(|error| error.to_string()),
+                version => Err(unsupported_version(version)),
+            }
+        }
+    }
+}
+
+fn unsupported_version(version: u32) -> String {
+    match version > FORMAT_VERSION {
+        true => format!(
+            "The file was saved with format version {}, but only versions up to {} are supported.",
+            version, FORMAT_VERSION
+        ),
+        false => format!(
+            "Format version {} does not exist in this file format.",
+            version
+        ),
+    }
+}
+
+#[derive(Deserialize)]
+struct SceneV0 {
+    entities: BTreeMap<u64, EntityV0>,
+}
+
+#[derive(Deserialize)]
+struct EntityV0 {
+    components: ComponentsV0,
+}
+
+#[derive(Deserialize)]
+struct PositionV0((f32, f32));
+
+#[derive(Deserialize)]
+struct ModelIdV0(Uuid);
+
+#[derive(Deserialize)]
+struct SignalStateV0 {
+    signal: SignalEntry,
+}
+
+#[derive(Deserialize)]
+struct PinModelV0 {
+    signal_state: SignalStateV0,
+    label: String,
+    uuid: Uuid,
+}
+
+#[derive(Deserialize)]
+struct PinModelCollectionV0(Vec<PinModelV0>);
+
+#[derive(Deserialize)]
+struct NamedV0 {
+    name: String,
+}
+
+#[derive(Deserialize)]
+enum WireNodeV0 {
+    Pin(Uuid),
+    Joint(Uuid),
+}
+
+#[derive(Deserialize)]
+struct WireNodesV0(Vec<WireNodeV0>);
+
+/// The components of a version 0 entity that are needed to rebuild the board.
+#[derive(Default)]
+struct ComponentsV0 {
+    position: Option<(f32, f32)>,
+    model_id: Option<Uuid>,
+    pins: Option<Vec<PinModelV0>>,
+    wire_nodes: Option<Vec<WireNodeV0>>,
+    kind: Option<String>,
+    properties: DeviceProperties,
+    is_wire: bool,
+    is_wire_joint: bool,
+}
+
+impl<'de> Deserialize<'de> for ComponentsV0 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(ComponentsV0Visitor)
+    }
+}
+
+struct ComponentsV0Visitor;
+
+impl<'de> Visitor<'de> for ComponentsV0Visitor {
+    type Value = ComponentsV0;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of components keyed by type path")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut components = ComponentsV0::default();
+
+        while let Some(type_path) = map.next_key::<String>()? {
+            match type_path.as_str() {
+                "logics::designer::position::Position" => {
+                    components.position = Some(map.next_value::<PositionV0>()?.0);
+                }
+                "logics::designer::model::ModelId" => {
+                    components.model_id = Some(map.next_value::<ModelIdV0>()?.0);
+                }
+                "logics::designer::pin::PinModelCollection" => {
+                    components.pins = Some(map.next_value::<PinModelCollectionV0>()?.0);
+                }
+                "logics::designer::wire::WireNodes" => {
+                    components.wire_nodes = Some(map.next_value::<WireNodesV0>()?.0);
+                }
+                "logics::designer::devices::generic_chip::GenericChip" => {
+                    components.kind = Some(map.next_value::<NamedV0>()?.name);
+                }
+                "logics::designer::devices::net_label::NetLabel" => {
+                    let net_label = map.next_value::<NamedV0>()?;
+                    components.kind = Some("NET-LABEL".into());
+                    components
+                        .properties
+                        .set("name", PropertyValue::Text(net_label.name));
+                }
+                "logics::designer::devices::binary_io::BinarySwitch" => {
+                    map.next_value::<IgnoredAny>()?;
+                    components.kind = Some("SWITCH".into());
+                }
+                "logics::designer::devices::binary_io::BinaryDisplay" => {
+                    map.next_value::<IgnoredAny>()?;
+                    components.kind = Some("DISPLAY".into());
+                }
+                // the period could not be changed in version 0, so the default is kept
+                "logics::designer::devices::clock::Clock" => {
+                    map.next_value::<IgnoredAny>()?;
+                    components.kind = Some("CLOCK".into());
+                }
+                "logics::designer::wire::WireModel" => {
+                    map.next_value::<IgnoredAny>()?;
+                    components.is_wire = true;
+                }
+                "logics::designer::wire::wire_joint::WireJointModel" => {
+                    map.next_value::<IgnoredAny>()?;
+                    components.is_wire_joint = true;
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        Ok(components)
+    }
+}
+
+fn read_v0(contents: &str) -> Result<SceneV0, String> {
+    ron::from_str(contents).map_err(|error| error.to_string())
+}
+
+/// Converts a version 0 scene into the current format.
+/// Entities that cant be identified are dropped and later reported by the validation.
+fn migrate_v0(scene: SceneV0) -> BoardFile {
+    let mut board = BoardFile::default();
+
+    for entity in scene.entities.into_values() {
+        let components = entity.components;
+        let id = components.model_id.unwrap_or_else(Uuid::new_v4);
+        let position = components.position.unwrap_or_default();
+
+        if let (Some(kind), Some(pins)) = (components.kind, components.pins) {
+            board.devices.push(DeviceEntry {
+                id,
+                kind,
+                position,
+                pins: pins
+                    .into_iter()
+                    .map(|pin_model| PinEntry {
+                        label: pin_model.label,
+                        id: pin_model.uuid,
+                        signal: pin_model.signal_state.signal,
+                    })
+                    .collect(),
+                properties: components.properties,
+            });
+        } else if components.is_wire {
+            board.wires.push(WireEntry {
+                id,
+                nodes: components
+                    .wire_nodes
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|wire_node| match wire_node {
+                        WireNodeV0::Pin(uuid) => WireNodeEntry::Pin(uuid),
+                        WireNodeV0::Joint(uuid) => WireNodeEntry::Joint(uuid),
+                    })
+                    .collect(),
+            });
+        } else if components.is_wire_joint {
+            board.wire_joints.push(WireJointEntry { id, position });
+        }
+    }
+
+    board
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICE_ID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+    const PIN_ID: &str = "0b5e7c5d-7a0d-4c57-a3f5-2b6c2a1e9d10";
+    const JOINT_ID: &str = "c3a1d7f2-5b8e-4f6a-9d2c-1e7b3a5c8f40";
+    const WIRE_ID: &str = "9f2b6e1a-3c4d-4e5f-8a7b-6c5d4e3f2a10";
+
+    fn scene_v0() -> String {
+        format!(
+            r#"(
+    resources: {{}},
+    entities: {{
+        4294967296: (
+            components: {{
+                "logics::designer::position::Position": ((10.0, 20.0)),
+                "logics::designer::model::ModelId": ("{DEVICE_ID}"),
+                "logics::designer::devices::binary_io::BinarySwitch": (),
+                "logics::designer::pin::PinModelCollection": ([
+                    (signal_state: (signal: High), label: "Q", uuid: "{PIN_ID}"),
+                ]),
+                "logics::designer::devices::unknown::Unknown": (value: 1),
+            }},
+        ),
+        4294967297: (
+            components: {{
+                "logics::designer::model::ModelId": ("{WIRE_ID}"),
+                "logics::designer::wire::WireModel": (),
+                "logics::designer::wire::WireNodes": ([Pin("{PIN_ID}"), Joint("{JOINT_ID}")]),
+            }},
+        ),
+        4294967298: (
+            components: {{
+                "logics::designer::position::Position": ((30.0, 40.0)),
+                "logics::designer::model::ModelId": ("{JOINT_ID}"),
+                "logics::designer::wire::wire_joint::WireJointModel": (),
+            }},
+        ),
+        4294967299: (
+            components: {{
+                "logics::designer::position::Position": ((50.0, 60.0)),
+            }},
+        ),
+    }},
+)"#
+        )
+    }
+
+    fn uuid(id: &str) -> Uuid {
+        Uuid::parse_str(id).unwrap()
+    }
+
+    #[test]
+    fn migrates_v0_scene() {
+        let board = read_board(scene_v0().as_bytes()).unwrap();
+
+        assert_eq!(board.version, FORMAT_VERSION);
+        assert_eq!(
+            board.devices,
+            vec![DeviceEntry {
+                id: uuid(DEVICE_ID),
+                kind: "SWITCH".into(),
+                position: (10.0, 20.0),
+                pins: vec![PinEntry {
+                    label: "Q".into(),
+                    id: uuid(PIN_ID),
+                    signal: SignalEntry::High,
+                }],
+                properties: DeviceProperties::default(),
+            }]
+        );
+        assert_eq!(
+            board.wires,
+            vec![WireEntry {
+                id: uuid(WIRE_ID),
+                nodes: vec![
+                    WireNodeEntry::Pin(uuid(PIN_ID)),
+                    WireNodeEntry::Joint(uuid(JOINT_ID)),
+                ],
+            }]
+        );
+        // the entity that is neither a device, wire nor joint is dropped
+        assert_eq!(
+            board.wire_joints,
+            vec![WireJointEntry {
+                id: uuid(JOINT_ID),
+                position: (30.0, 40.0),
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let error =
+            read_board(format!(r#"{{"version": {}}}"#, FORMAT_VERSION + 1).as_bytes()).unwrap_err();
+
+        assert!(error.contains("only versions up to"));
+    }
+}
@@ -1,16 +1,52 @@
-use std::{env::current_exe, path::PathBuf};
+pub mod autosave;
+pub mod encoding;
+pub mod format;
+pub mod library;
+pub mod memory_image;
+pub mod migration;
+pub mod recent_files;
+pub mod tabs;
+pub mod validation;
 
-use bevy::{prelude::*, tasks::AsyncComputeTaskPool, window::PrimaryWindow};
-use moonshine_save::{
-    file_from_event,
-    load::{load, unload},
-    save::{save_default, Save},
+use std::{env::current_exe, fs, path::PathBuf};
+
+use bevy::{
+    ecs::{component::Tick, system::SystemChangeTick},
+    prelude::*,
+    tasks::AsyncComputeTaskPool,
+    window::PrimaryWindow,
 };
+use moonshine_save::save::Save;
 use rfd::AsyncFileDialog;
 
 use crossbeam_channel::{bounded, Receiver, Sender};
 
-use crate::events::{LoadEvent, LoadRequestEvent, NewFileEvent, SaveEvent, SaveRequestEvent};
+use crate::events::{
+    DiscardRecoveryEvent, LoadEvent, LoadRequestEvent, NewFileEvent, OpenRecentFileEvent,
+    RestoreRecoveryEvent, SaveAsRequestEvent, SaveEvent, SaveRequestEvent,
+};
+
+use super::{
+    devices::net_label::NetLabel,
+    model::{ModelId, ModelRegistry},
+    position::Position,
+    wire::WireNodes,
+};
+
+use autosave::{
+    autosave_due, check_unclean_exit, clean_up_on_exit, discard_recovery, remove_recovery_file,
+    restore_recovery, tick_autosave_timer, write_recovery_files, AutosaveSettings, AutosaveTimer,
+    RecoveryPrompt,
+};
+use library::LibraryPlugin;
+use memory_image::MemoryImagePlugin;
+use recent_files::{load_recent_files, RecentFiles};
+use tabs::{BoardTabs, TabsPlugin};
+
+use encoding::{encode_board, SaveFormat};
+use format::{collect_board, spawn_board};
+use migration::read_board;
+use validation::repair_board;
 
 //UNSURE: might be better outside of designer
 pub struct SaveManagementPlugin;
@@ -26,6 +62,10 @@ impl Plugin for SaveManagementPlugin {
             Update,
             handle_save_request.run_if(on_event::<SaveRequestEvent>),
         );
+        app.add_systems(
+            Update,
+            handle_save_as_request.run_if(on_event::<SaveAsRequestEvent>),
+        );
 
         // pick load file
         let (ltx, lrx) = bounded::<LoadFilePick>(1);
@@ -36,33 +76,122 @@ impl Plugin for SaveManagementPlugin {
             Update,
             handle_load_request.run_if(on_event::<LoadRequestEvent>),
         );
+        app.add_systems(
+            Update,
+            handle_open_recent_file.run_if(on_event::<OpenRecentFileEvent>),
+        );
+
+        app.init_resource::<RecentFiles>()
+            .add_systems(Startup, load_recent_files);
+
+        app.add_plugins((LibraryPlugin, TabsPlugin, MemoryImagePlugin));
 
         app.add_systems(
             Update,
             update_window_title.run_if(resource_changed::<ActiveSaveFile>),
         );
 
-        app.init_resource::<ActiveSaveFile>();
+        app.init_resource::<ActiveSaveFile>()
+            .init_resource::<LoadReport>()
+            .init_resource::<DiscardChangesPrompt>();
+
+        app.add_systems(PostStartup, mark_board_saved)
+            .add_systems(Last, track_board_changes);
+
+        // autosave and crash recovery
+        app.init_resource::<AutosaveSettings>()
+            .init_resource::<AutosaveTimer>()
+            .init_resource::<RecoveryPrompt>()
+            .add_systems(Startup, check_unclean_exit)
+            .add_systems(
+                Update,
+                (
+                    tick_autosave_timer,
+                    write_recovery_files.run_if(autosave_due),
+                )
+                    .chain(),
+            )
+            .add_systems(
+                First,
+                restore_recovery.run_if(on_event::<RestoreRecoveryEvent>),
+            )
+            .add_systems(
+                Update,
+                discard_recovery.run_if(on_event::<DiscardRecoveryEvent>),
+            )
+            .add_systems(Last, clean_up_on_exit);
 
         app.add_systems(
             First,
             (
-                // Needs additional on_event condition because of the use of has_event in the moonshine_save crate.
-                // has_event doesnt consume the event and because of that it executes the pipeline multiple times per event which causes a crash.
-                // This might be fixed in the latest version of moonshine_save which is not yet published on crates io.
-                save_default()
-                    .into(file_from_event::<SaveEvent>())
-                    .run_if(on_event::<SaveEvent>),
-                load(file_from_event::<LoadEvent>()).run_if(on_event::<LoadEvent>),
+                save_board.run_if(on_event::<SaveEvent>),
+                load_board.run_if(on_event::<LoadEvent>),
             ),
         );
-        app.add_systems(Update, new_file.run_if(on_event::<NewFileEvent>));
+        app.add_systems(First, new_file.run_if(on_event::<NewFileEvent>));
     }
 }
 
 #[derive(Resource, Default)]
 pub struct ActiveSaveFile {
     pub path: Option<PathBuf>,
+    /// Set when the board changed since it was last saved, loaded or cleared.
+    /// Changes to the models themselves are found by [`track_board_changes`]. Every editor that changes
+    /// the state or properties of a device has to set this flag itself, because the simulation changes
+    /// the same components all the time.
+    pub modified: bool,
+    saved_tick: Tick,
+}
+
+/// An action that would throw away the current board.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PendingAction {
+    NewFile,
+    Load,
+    OpenRecent(PathBuf),
+    CloseTab(usize),
+}
+
+/// Asks the user before an action discards unsaved changes.
+#[derive(Resource, Default)]
+pub struct DiscardChangesPrompt {
+    pub pending: Option<PendingAction>,
+    /// Set by the prompt, so the resent action is not asked about again.
+    pub confirmed: bool,
+}
+
+impl DiscardChangesPrompt {
+    /// Returns true if the action can go ahead, otherwise opens the prompt for it.
+    pub fn allow(&mut self, active_save_file: &ActiveSaveFile, action: PendingAction) -> bool {
+        self.allow_if_unmodified(active_save_file.modified, action)
+    }
+
+    /// Like [`DiscardChangesPrompt::allow`] for boards that are not active.
+    pub fn allow_if_unmodified(&mut self, modified: bool, action: PendingAction) -> bool {
+        if !modified || self.confirmed {
+            self.confirmed = false;
+            return true;
+        }
+
+        self.pending = Some(action);
+        false
+    }
+}
+
+/// Problems that occurred while loading the last board.
+#[derive(Resource, Default)]
+pub struct LoadReport {
+    pub path: Option<PathBuf>,
+    /// Set if the board could not be loaded at all.
+    pub error: Option<String>,
+    /// Broken parts of the board that were removed or fixed while loading.
+    pub repairs: Vec<String>,
+}
+
+impl LoadReport {
+    pub fn has_problems(&self) -> bool {
+        self.error.is_some() || !self.repairs.is_empty()
+    }
 }
 
 #[derive(Deref)]
@@ -76,16 +205,153 @@ struct AsyncReceiver<T>(Receiver<T>);
 #[derive(Resource, Deref)]
 struct AsyncSender<T>(Sender<T>);
 
-pub fn new_file(
-    q_entities: Query<Entity, With<Save>>,
+pub fn new_file(world: &mut World) {
+    world.resource_mut::<Events<NewFileEvent>>().clear();
+
+    let allowed = world.resource_scope(|world, mut prompt: Mut<DiscardChangesPrompt>| {
+        prompt.allow(world.resource::<ActiveSaveFile>(), PendingAction::NewFile)
+    });
+
+    if !allowed {
+        return;
+    }
+
+    despawn_board(world);
+
+    world.resource_mut::<ActiveSaveFile>().path = None;
+    mark_board_saved(world);
+    remove_recovery_file(world, world.resource::<BoardTabs>().active_id());
+}
+
+/// Loads the board from the file of the last [`LoadEvent`].
+/// The current board is only replaced if the file could be read,
+/// and broken references in the loaded board are repaired instead of crashing later on.
+pub fn load_board(world: &mut World) {
+    let Some(path) = world
+        .resource_mut::<Events<LoadEvent>>()
+        .drain()
+        .last()
+        .map(|load_ev| load_ev.path)
+    else {
+        return;
+    };
+
+    let repairs = match replace_board(world, &path) {
+        Ok(repairs) => repairs,
+        Err(error) => {
+            error!("Failed to load {}: {}", path.display(), error);
+            *world.resource_mut::<LoadReport>() = LoadReport {
+                path: Some(path),
+                error: Some(error),
+                repairs: Vec::new(),
+            };
+            return;
+        }
+    };
+
+    world.resource_mut::<ActiveSaveFile>().path = Some(path.clone());
+    world.resource_mut::<RecentFiles>().push(path.clone());
+    mark_board_saved(world);
+    remove_recovery_file(world, world.resource::<BoardTabs>().active_id());
+
+    *world.resource_mut::<LoadReport>() = LoadReport {
+        path: Some(path),
+        error: None,
+        repairs,
+    };
+}
+
+/// Replaces the current board with the one stored in the file.
+/// Returns the repairs that were made, or an error if the file could not be read.
+pub fn replace_board(world: &mut World, path: &PathBuf) -> Result<Vec<String>, String> {
+    let board = fs::read(path)
+        .map_err(|error| error.to_string())
+        .and_then(|bytes| read_board(&bytes))?;
+
+    despawn_board(world);
+
+    let mut repairs = spawn_board(world, &board).repairs;
+    repairs.extend(repair_board(world));
+
+    Ok(repairs)
+}
+
+fn despawn_board(world: &mut World) {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, With<Save>>()
+        .iter(world)
+        .collect();
+
+    for entity in entities {
+        if let Ok(entity_mut) = world.get_entity_mut(entity) {
+            entity_mut.despawn_recursive();
+        }
+    }
+    world.flush();
+}
+
+/// Writes the board to the file of the last [`SaveEvent`].
+/// The file format is chosen by the extension of the file.
+pub fn save_board(world: &mut World) {
+    let Some(path) = world
+        .resource_mut::<Events<SaveEvent>>()
+        .drain()
+        .last()
+        .map(|save_ev| save_ev.path)
+    else {
+        return;
+    };
+
+    let board = collect_board(world);
+
+    let result = encode_board(&board, SaveFormat::from_path(&path))
+        .and_then(|bytes| fs::write(&path, bytes).map_err(|error| error.to_string()));
+
+    match result {
+        Ok(()) => {
+            world.resource_mut::<RecentFiles>().push(path);
+            mark_board_saved(world);
+            remove_recovery_file(world, world.resource::<BoardTabs>().active_id());
+        }
+        Err(error) => error!("Failed to save {}: {}", path.display(), error),
+    }
+}
+
+/// Treats the current state of the board as unmodified.
+pub fn mark_board_saved(world: &mut World) {
+    let tick = world.change_tick();
+    let mut active_save_file = world.resource_mut::<ActiveSaveFile>();
+    active_save_file.modified = false;
+    active_save_file.saved_tick = tick;
+}
+
+/// Sets the modified flag when models were added, removed, moved, rewired or renamed since the last save.
+/// Device state like stored values and memory contents is not watched, since it also changes while
+/// simulating. Edits of it mark the board as modified where they are made, see [`ActiveSaveFile::modified`].
+pub fn track_board_changes(
+    q_positions: Query<Ref<Position>, With<ModelId>>,
+    q_wire_nodes: Query<Ref<WireNodes>>,
+    q_net_labels: Query<Ref<NetLabel>>,
+    model_registry: Res<ModelRegistry>,
     mut active_save_file: ResMut<ActiveSaveFile>,
-    mut commands: Commands,
+    system_change_tick: SystemChangeTick,
 ) {
-    for entity in q_entities.iter() {
-        commands.entity(entity).despawn_recursive(); //TODO: try insert unload instead
+    if active_save_file.modified {
+        return;
     }
 
-    active_save_file.path = None;
+    let saved_tick = active_save_file.saved_tick;
+    let this_run = system_change_tick.this_run();
+    let is_modified = |tick: Tick| tick.is_newer_than(saved_tick, this_run);
+
+    let modified = is_modified(model_registry.last_changed())
+        || q_positions.iter().any(|p| is_modified(p.last_changed()))
+        || q_wire_nodes.iter().any(|w| is_modified(w.last_changed()))
+        || q_net_labels.iter().any(|n| is_modified(n.last_changed()));
+
+    if modified {
+        active_save_file.modified = true;
+    }
 }
 
 fn handle_save_file_picked_result(
@@ -103,11 +369,9 @@ fn handle_save_file_picked_result(
 
 fn handle_load_file_picked_result(
     receiver: Res<AsyncReceiver<LoadFilePick>>,
-    mut active_save_file: ResMut<ActiveSaveFile>,
     mut load_ev_writer: EventWriter<LoadEvent>,
 ) {
     for result in receiver.try_iter() {
-        active_save_file.path = Some(result.clone());
         load_ev_writer.send(LoadEvent {
             path: result.clone(),
         });
@@ -126,14 +390,32 @@ fn handle_save_request(
         return;
     }
 
-    let sender = sender.clone();
+    open_save_dialog(&sender, &active_save_file);
+}
+
+/// Always asks for a new file to save to, even if the board already has one.
+fn handle_save_as_request(
+    sender: Res<AsyncSender<SaveFilePick>>,
+    active_save_file: Res<ActiveSaveFile>,
+) {
+    open_save_dialog(&sender, &active_save_file);
+}
+
+fn open_save_dialog(sender: &AsyncSender<SaveFilePick>, active_save_file: &ActiveSaveFile) {
+    let sender = sender.0.clone();
+    let file_name = active_save_file
+        .path
+        .as_ref()
+        .and_then(|path| path.file_name())
+        .and_then(|file_name| file_name.to_str())
+        .unwrap_or("save.ron")
+        .to_string();
 
     AsyncComputeTaskPool::get()
         .spawn(async move {
-            let result = AsyncFileDialog::new()
-                .add_filter("saves", &["ron"])
+            let result = add_save_format_filters(AsyncFileDialog::new())
                 .set_directory(get_saves_folder())
-                .set_file_name("save.ron")
+                .set_file_name(file_name)
                 .save_file()
                 .await;
 
@@ -146,16 +428,47 @@ fn handle_save_request(
         .detach();
 }
 
-fn handle_load_request(sender: Res<AsyncSender<LoadFilePick>>) {
+fn handle_open_recent_file(
+    mut open_recent_events: EventReader<OpenRecentFileEvent>,
+    active_save_file: Res<ActiveSaveFile>,
+    mut discard_changes_prompt: ResMut<DiscardChangesPrompt>,
+    mut load_ev_writer: EventWriter<LoadEvent>,
+) {
+    let Some(open_recent_ev) = open_recent_events.read().last() else {
+        return;
+    };
+
+    let path = open_recent_ev.path.clone();
+    if !discard_changes_prompt.allow(&active_save_file, PendingAction::OpenRecent(path.clone())) {
+        return;
+    }
+
+    load_ev_writer.send(LoadEvent { path });
+}
+
+fn handle_load_request(
+    sender: Res<AsyncSender<LoadFilePick>>,
+    active_save_file: Res<ActiveSaveFile>,
+    mut discard_changes_prompt: ResMut<DiscardChangesPrompt>,
+) {
+    if !discard_changes_prompt.allow(&active_save_file, PendingAction::Load) {
+        return;
+    }
+
     let sender = sender.clone();
 
     AsyncComputeTaskPool::get()
         .spawn(async move {
-            let result = AsyncFileDialog::new()
-                .add_filter("saves", &["ron"])
-                .set_directory(get_saves_folder())
-                .pick_file()
-                .await;
+            let extensions: Vec<&str> = SaveFormat::ALL
+                .iter()
+                .map(|format| format.extension())
+                .collect();
+
+            let result =
+                add_save_format_filters(AsyncFileDialog::new().add_filter("saves", &extensions))
+                    .set_directory(get_saves_folder())
+                    .pick_file()
+                    .await;
 
             if let Some(file_handle) = result {
                 sender
@@ -166,6 +479,13 @@ fn handle_load_request(sender: Res<AsyncSender<LoadFilePick>>) {
         .detach();
 }
 
+/// Adds a filter for every [`SaveFormat`], the chosen extension decides the format when saving.
+fn add_save_format_filters(dialog: AsyncFileDialog) -> AsyncFileDialog {
+    SaveFormat::ALL.iter().fold(dialog, |dialog, format| {
+        dialog.add_filter(format.name(), &[format.extension()])
+    })
+}
+
 /// Gets the "saves" folder that is relative to the executable.
 fn get_saves_folder() -> PathBuf {
     let mut exe_path = current_exe().expect("Failed to get current executable path");
@@ -183,6 +503,10 @@ fn update_window_title(
             Some(path) => path.file_name().unwrap().to_str().unwrap().to_string(),
             None => "New File".into(),
         };
+
+        if active_save_file.modified {
+            window.title.push('*');
+        }
     } else {
         eprintln!("Failed to get primary window");
     }
@@ -0,0 +1,54 @@
+use std::{fs, path::PathBuf};
+
+use bevy::{prelude::*, scene::ron};
+
+use super::get_saves_folder;
+
+/// Amount of files that are remembered.
+const MAX_RECENT_FILES: usize = 10;
+
+/// Recently saved or loaded files, most recent first.
+/// Persisted next to the saves folder, so the list survives restarts.
+#[derive(Resource, Default)]
+pub struct RecentFiles {
+    pub paths: Vec<PathBuf>,
+}
+
+impl RecentFiles {
+    /// Moves the path to the front of the list and persists it.
+    pub fn push(&mut self, path: PathBuf) {
+        self.paths.retain(|recent_path| *recent_path != path);
+        self.paths.insert(0, path);
+        self.paths.truncate(MAX_RECENT_FILES);
+        self.store();
+    }
+
+    fn store(&self) {
+        let recent_files_file = get_recent_files_file();
+
+        let result = ron::ser::to_string_pretty(&self.paths, ron::ser::PrettyConfig::default())
+            .map_err(|error| error.to_string())
+            .and_then(|contents| {
+                fs::write(&recent_files_file, contents).map_err(|error| error.to_string())
+            });
+
+        if let Err(error) = result {
+            error!("Failed to store {}: {}", recent_files_file.display(), error);
+        }
+    }
+}
+
+fn get_recent_files_file() -> PathBuf {
+    get_saves_folder().with_file_name("recent_files.ron")
+}
+
+pub fn load_recent_files(mut recent_files: ResMut<RecentFiles>) {
+    let Ok(contents) = fs::read_to_string(get_recent_files_file()) else {
+        return;
+    };
+
+    match ron::from_str::<Vec<PathBuf>>(&contents) {
+        Ok(paths) => recent_files.paths = paths,
+        Err(error) => warn!("Ignoring broken recent files list: {}", error),
+    }
+}
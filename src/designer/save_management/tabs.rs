@@ -0,0 +1,334 @@
+//! Several boards can be open at once as tabs.
+//!
+//! Only the active tab is spawned in the world, so all systems, the [`ModelRegistry`] and the
+//! simulation only ever see the active board. Inactive tabs are kept as [`BoardFile`] snapshots
+//! together with their [`ActiveSaveFile`], camera and simulation state, and are swapped in when
+//! switching tabs. Their simulation is paused until they become active again.
+//!
+//! Save files only contain the current signal of each pin, so the simulation state they leave out
+//! is kept next to the snapshot: the signals of the tick before, the signals of wires and joints
+//! and the time since the last tick of every clock. Without them, flip-flops would see clock edges
+//! that never happened and clocks would start their period over when a tab becomes active again.
+//!
+//! [`ModelRegistry`]: crate::designer::model::ModelRegistry
+
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use bevy::prelude::*;
+use uuid::Uuid;
+
+use crate::{
+    designer::{
+        devices::clock::Clock,
+        model::ModelId,
+        pin::PinModelCollection,
+        signal::{Signal, SignalState},
+    },
+    events::{CloseTabEvent, NewTabEvent, SwitchTabEvent},
+};
+
+use super::{
+    autosave::remove_recovery_file,
+    despawn_board,
+    format::{collect_board, spawn_board, BoardFile},
+    mark_board_saved, ActiveSaveFile, DiscardChangesPrompt, PendingAction,
+};
+
+pub struct TabsPlugin;
+
+impl Plugin for TabsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BoardTabs>().add_systems(
+            First,
+            (
+                new_tab.run_if(on_event::<NewTabEvent>),
+                switch_tab.run_if(on_event::<SwitchTabEvent>),
+                close_tab.run_if(on_event::<CloseTabEvent>),
+            )
+                .chain(),
+        );
+    }
+}
+
+/// Everything that belongs to a board that is not spawned.
+#[derive(Default)]
+pub struct BoardTab {
+    /// Stays the same while the tab is open, even when tabs before it are closed.
+    id: Uuid,
+    pub path: Option<PathBuf>,
+    pub modified: bool,
+    /// Outdated while the tab is active, the spawned models are the current state.
+    board: BoardFile,
+    /// Previous signal of every pin of the board, by pin id.
+    previous_signals: HashMap<Uuid, Signal>,
+    /// Signals of the wires and joints, by model id.
+    signal_states: HashMap<Uuid, SignalState>,
+    /// Time since the last tick of every clock, by model id.
+    clock_elapsed: HashMap<Uuid, Duration>,
+    camera: Option<(Transform, f32)>,
+    simulation_paused: bool,
+    simulation_speed: f32,
+}
+
+impl BoardTab {
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// The board as it was when the tab was last active.
+    pub fn board(&self) -> &BoardFile {
+        &self.board
+    }
+
+    fn new() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            simulation_speed: 1.0,
+            ..default()
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .and_then(|file_name| file_name.to_str())
+            .unwrap_or("New File")
+            .to_string()
+    }
+}
+
+#[derive(Resource)]
+pub struct BoardTabs {
+    pub tabs: Vec<BoardTab>,
+    pub active: usize,
+}
+
+impl BoardTabs {
+    pub fn active_id(&self) -> Uuid {
+        self.tabs[self.active].id
+    }
+}
+
+impl Default for BoardTabs {
+    fn default() -> Self {
+        Self {
+            tabs: vec![BoardTab::new()],
+            active: 0,
+        }
+    }
+}
+
+/// Opens an empty board in a new tab.
+pub fn new_tab(world: &mut World) {
+    world.resource_mut::<Events<NewTabEvent>>().clear();
+
+    store_active_tab(world);
+    despawn_board(world);
+
+    let mut board_tabs = world.resource_mut::<BoardTabs>();
+    board_tabs.tabs.push(BoardTab::new());
+    board_tabs.active = board_tabs.tabs.len() - 1;
+
+    restore_active_tab(world);
+}
+
+pub fn switch_tab(world: &mut World) {
+    let Some(index) = world
+        .resource_mut::<Events<SwitchTabEvent>>()
+        .drain()
+        .last()
+        .map(|switch_ev| switch_ev.index)
+    else {
+        return;
+    };
+
+    let board_tabs = world.resource::<BoardTabs>();
+    if index == board_tabs.active || index >= board_tabs.tabs.len() {
+        return;
+    }
+
+    store_active_tab(world);
+    despawn_board(world);
+    world.resource_mut::<BoardTabs>().active = index;
+    restore_active_tab(world);
+}
+
+/// Closes a tab after asking about its unsaved changes.
+/// The last tab is never closed but cleared instead.
+pub fn close_tab(world: &mut World) {
+    let Some(index) = world
+        .resource_mut::<Events<CloseTabEvent>>()
+        .drain()
+        .last()
+        .map(|close_ev| close_ev.index)
+    else {
+        return;
+    };
+
+    let board_tabs = world.resource::<BoardTabs>();
+    let Some(tab) = board_tabs.tabs.get(index) else {
+        return;
+    };
+
+    let tab_id = tab.id();
+    let is_active = index == board_tabs.active;
+    let modified = match is_active {
+        true => world.resource::<ActiveSaveFile>().modified,
+        false => tab.modified,
+    };
+
+    let allowed = world
+        .resource_mut::<DiscardChangesPrompt>()
+        .allow_if_unmodified(modified, PendingAction::CloseTab(index));
+    if !allowed {
+        return;
+    }
+
+    // the changes were discarded on purpose
+    remove_recovery_file(world, tab_id);
+
+    if !is_active {
+        let mut board_tabs = world.resource_mut::<BoardTabs>();
+        board_tabs.tabs.remove(index);
+        if index < board_tabs.active {
+            board_tabs.active -= 1;
+        }
+        return;
+    }
+
+    despawn_board(world);
+
+    let mut board_tabs = world.resource_mut::<BoardTabs>();
+    board_tabs.tabs.remove(index);
+    if board_tabs.tabs.is_empty() {
+        board_tabs.tabs.push(BoardTab::new());
+    }
+    board_tabs.active = index.min(board_tabs.tabs.len() - 1);
+
+    restore_active_tab(world);
+}
+
+/// Writes the spawned board and its state into the active tab.
+fn store_active_tab(world: &mut World) {
+    let board = collect_board(world);
+
+    let previous_signals = world
+        .query::<&PinModelCollection>()
+        .iter(world)
+        .flat_map(|pin_model_collection| pin_model_collection.iter())
+        .map(|pin_model| {
+            (
+                pin_model.uuid,
+                pin_model.signal_state.get_previous_signal().clone(),
+            )
+        })
+        .collect();
+
+    let signal_states = world
+        .query::<(&ModelId, &SignalState)>()
+        .iter(world)
+        .map(|(model_id, signal_state)| (model_id.0, signal_state.clone()))
+        .collect();
+
+    let clock_elapsed = world
+        .query::<(&ModelId, &Clock)>()
+        .iter(world)
+        .map(|(model_id, clock)| (model_id.0, clock.elapsed()))
+        .collect();
+
+    let active_save_file = world.resource::<ActiveSaveFile>();
+    let path = active_save_file.path.clone();
+    let modified = active_save_file.modified;
+
+    let camera = world
+        .query_filtered::<(&Transform, &OrthographicProjection), With<Camera2d>>()
+        .get_single(world)
+        .map(|(transform, projection)| (*transform, projection.scale))
+        .ok();
+
+    let time = world.resource::<Time<Virtual>>();
+    let simulation_paused = time.is_paused();
+    let simulation_speed = time.relative_speed();
+
+    let mut board_tabs = world.resource_mut::<BoardTabs>();
+    let active = board_tabs.active;
+    let id = board_tabs.tabs[active].id;
+    board_tabs.tabs[active] = BoardTab {
+        id,
+        path,
+        modified,
+        board,
+        previous_signals,
+        signal_states,
+        clock_elapsed,
+        camera,
+        simulation_paused,
+        simulation_speed,
+    };
+}
+
+/// Spawns the board of the active tab and restores its state.
+fn restore_active_tab(world: &mut World) {
+    let mut board_tabs = world.resource_mut::<BoardTabs>();
+    let active = board_tabs.active;
+    let tab = std::mem::take(&mut board_tabs.tabs[active]);
+
+    for repair in spawn_board(world, &tab.board).repairs {
+        warn!("{}", repair);
+    }
+
+    for mut pin_model_collection in world.query::<&mut PinModelCollection>().iter_mut(world) {
+        for pin_model in pin_model_collection.iter_mut() {
+            if let Some(previous_signal) = tab.previous_signals.get(&pin_model.uuid) {
+                pin_model
+                    .signal_state
+                    .restore_previous_signal(previous_signal.clone());
+            }
+        }
+    }
+
+    for (model_id, mut signal_state) in world
+        .query::<(&ModelId, &mut SignalState)>()
+        .iter_mut(world)
+    {
+        if let Some(stored_signal_state) = tab.signal_states.get(&model_id.0) {
+            *signal_state = stored_signal_state.clone();
+        }
+    }
+
+    for (model_id, mut clock) in world.query::<(&ModelId, &mut Clock)>().iter_mut(world) {
+        if let Some(elapsed) = tab.clock_elapsed.get(&model_id.0) {
+            clock.set_elapsed(*elapsed);
+        }
+    }
+
+    if let Some((camera_transform, camera_scale)) = tab.camera {
+        if let Ok((mut transform, mut projection)) = world
+            .query_filtered::<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>()
+            .get_single_mut(world)
+        {
+            *transform = camera_transform;
+            projection.scale = camera_scale;
+        }
+    }
+
+    let mut time = world.resource_mut::<Time<Virtual>>();
+    time.set_relative_speed(tab.simulation_speed);
+    match tab.simulation_paused {
+        true => time.pause(),
+        false => time.unpause(),
+    }
+
+    world.resource_mut::<ActiveSaveFile>().path = tab.path.clone();
+    mark_board_saved(world);
+    world.resource_mut::<ActiveSaveFile>().modified = tab.modified;
+
+    // keep the name and modified flag for the tab bar, the board itself is outdated from now on
+    world.resource_mut::<BoardTabs>().tabs[active] = BoardTab {
+        id: tab.id,
+        path: tab.path,
+        modified: tab.modified,
+        ..BoardTab::new()
+    };
+}
@@ -0,0 +1,298 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use uuid::Uuid;
+
+use crate::designer::{
+    devices::{
+        device::{DeviceModel, DeviceSerializers},
+        generic_chip::GenericChip,
+    },
+    model::{ModelId, ModelRegistry},
+    pin::PinModelCollection,
+    position::Position,
+    signal::{Signal, SignalState},
+    wire::{wire_joint::WireJointModel, WireModel, WireNode, WireNodes},
+};
+
+/// Checks a freshly loaded board for broken references and repairs them where possible.
+/// Returns a readable description of every repair that was made.
+pub fn repair_board(world: &mut World) -> Vec<String> {
+    let mut repairs: Vec<String> = Vec::new();
+
+    repair_model_ids(world, &mut repairs);
+    repair_duplicate_ids(world, &mut repairs);
+    repair_devices(world, &mut repairs);
+    world.flush();
+    repair_wires(world, &mut repairs);
+    world.flush();
+    repair_wire_joints(world, &mut repairs);
+    world.flush();
+
+    repairs
+}
+
+/// Gives every model without a [`ModelId`] a new one, so it can be referenced again.
+fn repair_model_ids(world: &mut World, repairs: &mut Vec<String>) {
+    let entities: Vec<Entity> = world
+        .query_filtered::<Entity, (
+            Or<(With<DeviceModel>, With<WireModel>, With<WireJointModel>)>,
+            Without<ModelId>,
+        )>()
+        .iter(world)
+        .collect();
+
+    for entity in entities {
+        world.entity_mut(entity).insert(ModelId::new());
+        repairs.push("Assigned a new id to a model without one.".into());
+    }
+}
+
+/// Gives models and pins that share their id with another one a new id.
+/// Connections keep referring to the first one.
+fn repair_duplicate_ids(world: &mut World, repairs: &mut Vec<String>) {
+    let models: Vec<(Entity, Uuid)> = world
+        .query_filtered::<(Entity, &ModelId), Or<(
+            With<DeviceModel>,
+            With<WireModel>,
+            With<WireJointModel>,
+        )>>()
+        .iter(world)
+        .map(|(entity, model_id)| (entity, model_id.0))
+        .collect();
+
+    let mut first_models: HashMap<Uuid, Entity> = HashMap::new();
+    let mut duplicate_models: Vec<Entity> = Vec::new();
+    for (entity, uuid) in models {
+        match first_models.get(&uuid) {
+            Some(_) => duplicate_models.push(entity),
+            None => {
+                first_models.insert(uuid, entity);
+            }
+        }
+    }
+
+    if !duplicate_models.is_empty() {
+        for entity in duplicate_models.iter() {
+            let mut model = world.entity_mut(*entity);
+            model.remove::<ModelId>();
+            model.insert(ModelId::new());
+        }
+
+        // removing the duplicates also removed the registry entries of the models they shared the id with
+        for (uuid, entity) in first_models {
+            let mut model = world.entity_mut(entity);
+            model.remove::<ModelId>();
+            model.insert(ModelId(uuid));
+        }
+
+        repairs.push(format!(
+            "Assigned new ids to {} model(s) that shared their id with another one.",
+            duplicate_models.len()
+        ));
+    }
+
+    let mut pin_uuids: HashSet<Uuid> = HashSet::new();
+    let mut duplicate_pins = 0;
+    for mut pin_model_collection in world.query::<&mut PinModelCollection>().iter_mut(world) {
+        for pin_model in pin_model_collection.iter_mut() {
+            if !pin_uuids.insert(pin_model.uuid) {
+                pin_model.uuid = Uuid::new_v4();
+                pin_uuids.insert(pin_model.uuid);
+                duplicate_pins += 1;
+            }
+        }
+    }
+
+    if duplicate_pins > 0 {
+        repairs.push(format!(
+            "Assigned new ids to {} pin(s) that shared their id with another one.",
+            duplicate_pins
+        ));
+    }
+}
+
+/// Removes devices that are missing required components or are unknown, and rebuilds pins that
+/// do not match the device.
+/// The pins are compared to the ones of a freshly spawned device with the same properties, because
+/// the simulation and the views expect every pin of a device to exist.
+fn repair_devices(world: &mut World, repairs: &mut Vec<String>) {
+    let Some(device_serializers) = world.remove_resource::<DeviceSerializers>() else {
+        return;
+    };
+
+    let mut template_world = World::new();
+    template_world.init_resource::<ModelRegistry>();
+
+    let mut invalid_devices: Vec<(Entity, String)> = Vec::new();
+    let mut rebuilt_devices: Vec<(Entity, PinModelCollection, String)> = Vec::new();
+
+    let mut q_devices = world.query_filtered::<(
+        EntityRef,
+        Option<&Position>,
+        Option<&PinModelCollection>,
+    ), With<DeviceModel>>();
+
+    for (device, position, pin_model_collection) in q_devices.iter(world) {
+        let (Some(_), Some(pin_model_collection)) = (position, pin_model_collection) else {
+            invalid_devices.push((
+                device.id(),
+                "Removed a device without position or pins.".into(),
+            ));
+            continue;
+        };
+
+        let Some((kind, serializer)) = device_serializers.device_id(&device).and_then(|kind| {
+            device_serializers
+                .0
+                .get(&kind)
+                .map(|serializer| (kind, serializer))
+        }) else {
+            let name = device
+                .get::<GenericChip>()
+                .map(|generic_chip| generic_chip.name.clone())
+                .unwrap_or_default();
+            invalid_devices.push((device.id(), format!("Removed unknown device \"{}\".", name)));
+            continue;
+        };
+
+        let properties = (serializer.save_properties)(&device);
+        let template_entity = (serializer.spawn)(&mut template_world, Position::ZERO);
+        (serializer.load_properties)(&mut template_world.entity_mut(template_entity), &properties);
+        let Some(template_pins) = template_world.get::<PinModelCollection>(template_entity) else {
+            continue;
+        };
+
+        let mut labels: Vec<&String> = pin_model_collection.iter().map(|pin| &pin.label).collect();
+        let mut template_labels: Vec<&String> =
+            template_pins.iter().map(|pin| &pin.label).collect();
+        labels.sort();
+        template_labels.sort();
+
+        if labels == template_labels {
+            continue;
+        }
+
+        // pins that match by label keep their id, so their wires stay connected
+        let mut rebuilt_pins = template_pins.clone();
+        for pin_model in rebuilt_pins.iter_mut() {
+            if let Some(existing_pin) = pin_model_collection
+                .iter()
+                .find(|existing_pin| existing_pin.label == pin_model.label)
+            {
+                pin_model.uuid = existing_pin.uuid;
+                pin_model.signal_state = existing_pin.signal_state.clone();
+            }
+        }
+
+        rebuilt_devices.push((
+            device.id(),
+            rebuilt_pins,
+            format!("Rebuilt the pins of {} to match the device.", kind),
+        ));
+    }
+
+    world.insert_resource(device_serializers);
+
+    for (entity, repair) in invalid_devices {
+        world.entity_mut(entity).despawn_recursive();
+        repairs.push(repair);
+    }
+
+    for (entity, pin_model_collection, repair) in rebuilt_devices {
+        world.entity_mut(entity).insert(pin_model_collection);
+        repairs.push(repair);
+    }
+}
+
+/// Removes connections to pins or wire joints that dont exist.
+/// Wires that are left with less than two connections are removed entirely.
+fn repair_wires(world: &mut World, repairs: &mut Vec<String>) {
+    let pin_uuids: HashSet<Uuid> = world
+        .query::<&PinModelCollection>()
+        .iter(world)
+        .flat_map(|pin_model_collection| pin_model_collection.iter().map(|pin| pin.uuid))
+        .collect();
+
+    let joint_uuids: HashSet<Uuid> = world
+        .query_filtered::<&ModelId, With<WireJointModel>>()
+        .iter(world)
+        .map(|model_id| model_id.0)
+        .collect();
+
+    let wires: Vec<(Entity, WireNodes, bool)> = world
+        .query_filtered::<(Entity, &WireNodes, Has<SignalState>), With<WireModel>>()
+        .iter(world)
+        .map(|(entity, wire_nodes, has_signal_state)| {
+            (entity, wire_nodes.clone(), has_signal_state)
+        })
+        .collect();
+
+    for (wire_entity, wire_nodes, has_signal_state) in wires {
+        let valid_nodes: Vec<WireNode> = wire_nodes
+            .0
+            .iter()
+            .filter(|wire_node| match wire_node {
+                WireNode::Pin(uuid) => pin_uuids.contains(uuid),
+                WireNode::Joint(uuid) => joint_uuids.contains(uuid),
+            })
+            .cloned()
+            .collect();
+
+        if valid_nodes.len() < 2 {
+            world.entity_mut(wire_entity).despawn_recursive();
+            repairs.push("Removed a wire that was not connected to anything.".into());
+            continue;
+        }
+
+        let removed_nodes = wire_nodes.0.len() - valid_nodes.len();
+        let mut wire = world.entity_mut(wire_entity);
+
+        if removed_nodes > 0 {
+            wire.insert(WireNodes(valid_nodes));
+            repairs.push(format!(
+                "Removed {} broken connection(s) from a wire.",
+                removed_nodes
+            ));
+        }
+
+        if !has_signal_state {
+            wire.insert(SignalState::new(Signal::Low));
+        }
+    }
+}
+
+/// Removes wire joints that are not used by any wire.
+fn repair_wire_joints(world: &mut World, repairs: &mut Vec<String>) {
+    let used_joints: HashSet<Uuid> = world
+        .query::<&WireNodes>()
+        .iter(world)
+        .flat_map(|wire_nodes| wire_nodes.0.iter())
+        .filter_map(|wire_node| match wire_node {
+            WireNode::Joint(uuid) => Some(*uuid),
+            WireNode::Pin(_) => None,
+        })
+        .collect();
+
+    let joints: Vec<(Entity, Uuid, bool, bool)> = world
+        .query_filtered::<(Entity, &ModelId, Has<Position>, Has<SignalState>), With<WireJointModel>>()
+        .iter(world)
+        .map(|(entity, model_id, has_position, has_signal_state)| {
+            (entity, model_id.0, has_position, has_signal_state)
+        })
+        .collect();
+
+    for (joint_entity, joint_uuid, has_position, has_signal_state) in joints {
+        if !used_joints.contains(&joint_uuid) || !has_position {
+            world.entity_mut(joint_entity).despawn_recursive();
+            repairs.push("Removed an unused or broken wire joint.".into());
+            continue;
+        }
+
+        if !has_signal_state {
+            world
+                .entity_mut(joint_entity)
+                .insert(SignalState::new(Signal::Low));
+        }
+    }
+}
@@ -13,7 +13,7 @@ use moonshine_view::{View, Viewable};
 
 use crate::{
     events::{DeleteEvent, SelectAllEvent},
-    find_descendant, get_cursor, get_cursor_mut, get_model,
+    find_descendant, get_cursor, get_cursor_mut,
     ui::cursor_captured::IsCursorCaptured,
 };
 
@@ -21,11 +21,9 @@ use super::{
     bounding_box::{BoundingBox, BoundingShape},
     cursor::{Cursor, CursorState},
     devices::device::{DeviceModel, DeviceView, DeviceViewKind},
-    model::{ModelId, ModelRegistry},
-    pin::PinView,
     position::Position,
     render_settings::CircuitBoardRenderingSettings,
-    wire::{create_wire, wire_joint::WireJointModel, WireModel, WireNode, WireNodes, WireView},
+    wire::{create_wire, routing::WireRoute, wire_joint::WireJointModel, WireModel, WireView},
 };
 
 //TODO: split file, highlighting, selecting(box)
@@ -71,7 +69,11 @@ pub struct DeviceSelectionOutlineBundle {
 }
 
 impl DeviceSelectionOutlineBundle {
-    pub fn new(render_settings: &CircuitBoardRenderingSettings, extents: Vec2) -> Self {
+    pub fn new(
+        render_settings: &CircuitBoardRenderingSettings,
+        extents: Vec2,
+        offset: Vec2,
+    ) -> Self {
         Self {
             selection_outline: DeviceSelectionOutline,
             stroke: Stroke::new(
@@ -83,7 +85,7 @@ impl DeviceSelectionOutlineBundle {
                     extents,
                     ..default()
                 }),
-                transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                transform: Transform::from_translation(offset.extend(1.0)),
                 ..default()
             },
         }
@@ -423,7 +425,11 @@ pub fn highlight_selected_devices(
                 _ => panic!("invalid bounding shape on device"),
             };
 
-            cb.spawn(DeviceSelectionOutlineBundle::new(&render_settings, extents));
+            cb.spawn(DeviceSelectionOutlineBundle::new(
+                &render_settings,
+                extents,
+                bbox.offset,
+            ));
         });
     }
 
@@ -443,23 +449,18 @@ pub fn highlight_selected_devices(
 }
 
 //TODO: performance, and really bad implementation
-#[allow(clippy::too_many_arguments)]
 pub fn highlight_selected_wires(
-    q_selected_wires: Query<(&WireNodes, &Viewable<WireModel>), With<Selected>>,
+    q_selected_wires: Query<&Viewable<WireModel>, With<Selected>>,
     q_wires: Query<&Viewable<WireModel>>,
     mut q_deselected: RemovedComponents<Selected>,
     mut q_selection_outlines: Query<(Entity, &Parent, &mut Path), With<WireSelectionOutline>>,
     mut commands: Commands,
     render_settings: Res<CircuitBoardRenderingSettings>,
-    q_pins: Query<(&GlobalTransform, &PinView)>,
-    q_wire_joints: Query<(&ModelId, &Position), With<WireJointModel>>,
-    q_parents: Query<&Parent>,
-    q_wire_views: Query<&View<WireModel>>,
+    q_wire_routes: Query<&WireRoute, With<WireView>>,
     q_children: Query<&Children>,
-    model_registry: Res<ModelRegistry>,
 ) {
     // add outline if missing
-    for (_, viewable) in q_selected_wires.iter() {
+    for viewable in q_selected_wires.iter() {
         let view_entity = viewable.view().entity();
         let mut has_outline = false;
         find_descendant!(q_children, view_entity, q_selection_outlines, |_| {
@@ -488,38 +489,10 @@ pub fn highlight_selected_wires(
         }
     }
 
-    for (selection_outline_entity, _, mut selection_outline_path) in q_selection_outlines.iter_mut()
-    {
-        if let Some((wire_nodes, _)) = get_model!(
-            q_parents,
-            q_wire_views,
-            q_selected_wires,
-            selection_outline_entity
-        ) {
-            //TODO: duplicate code
-            let points: Vec<Vec2> = wire_nodes
-                .0
-                .iter()
-                .map(|wire_node| match wire_node {
-                    WireNode::Joint(joint_uuid) => {
-                        q_wire_joints
-                            .get(model_registry.get_model_entity(joint_uuid))
-                            .unwrap()
-                            .1
-                             .0
-                    }
-                    WireNode::Pin(pin_uuid) => q_pins
-                        .iter()
-                        .find(|(_, pin_view)| pin_view.uuid == *pin_uuid)
-                        .unwrap()
-                        .0
-                        .translation()
-                        .truncate(),
-                })
-                .collect();
-
+    for (_, wire_view_entity, mut selection_outline_path) in q_selection_outlines.iter_mut() {
+        if let Ok(wire_route) = q_wire_routes.get(wire_view_entity.get()) {
             *selection_outline_path = ShapePath::build_as(&shapes::Polygon {
-                points,
+                points: wire_route.points.clone(),
                 closed: false,
             });
         }
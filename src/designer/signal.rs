@@ -19,6 +19,15 @@ impl Signal {
     }
 }
 
+impl From<bool> for Signal {
+    fn from(value: bool) -> Self {
+        match value {
+            true => Signal::High,
+            false => Signal::Low,
+        }
+    }
+}
+
 #[derive(PartialEq, Clone, Debug, Component, Reflect)]
 #[reflect(Component)]
 pub struct SignalState {
@@ -48,6 +57,12 @@ impl SignalState {
         self.next_signals.last().unwrap_or(&self.signal)
     }
 
+    /// Sets the signal of the last tick, which is not part of save files, so edges are detected
+    /// the same way as before the board was despawned.
+    pub fn restore_previous_signal(&mut self, previous_signal: Signal) {
+        self.previous_signal = previous_signal;
+    }
+
     pub fn set_signal(&mut self, signal: Signal) {
         self.next_signals.clear();
         self.next_signals.push(signal);
@@ -2,14 +2,16 @@ use bevy::prelude::*;
 use bevy_prototype_lyon::prelude::*;
 use moonshine_core::object::Object;
 use moonshine_view::{BuildView, RegisterView, ViewCommands, Viewable};
+use routing::{toggle_wire_routing_mode, update_wire_routes, WireRoute, WireRoutingMode};
 use uuid::Uuid;
 use wire_joint::{create_wire_joint, WireJointModel};
 
+pub mod routing;
 pub mod wire_joint;
 
 use crate::{
-    get_cursor, get_cursor_mut, simulation::simulation::propagate_signals,
-    ui::cursor_captured::IsCursorCaptured,
+    events::ToggleWireRoutingEvent, get_cursor, get_cursor_mut,
+    simulation::simulation::propagate_signals, ui::cursor_captured::IsCursorCaptured,
 };
 
 use super::{
@@ -24,7 +26,6 @@ use super::{
 };
 
 //TODO: refactor highlight code (observers)
-//TODO: fix line jank (LineList)
 //TODO: split into files
 //TODO: Only ever access model, view only accessed from model itself for syncing
 pub struct WirePlugin;
@@ -33,7 +34,8 @@ impl Plugin for WirePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<WireNodes>()
             .register_type::<WireJointModel>()
-            .register_type::<WireModel>();
+            .register_type::<WireModel>()
+            .init_resource::<WireRoutingMode>();
 
         app.add_observer(on_create_wire);
         app.add_observer(on_remove_wire);
@@ -50,12 +52,24 @@ impl Plugin for WirePlugin {
                 .chain()
                 .run_if(resource_equals(IsCursorCaptured(false))),
         )
-        .add_systems(Update, (cancel_wire_placement, update_wire_views).chain())
+        .add_systems(
+            Update,
+            (cancel_wire_placement, update_wire_routes, update_wire_views).chain(),
+        )
+        .add_systems(
+            Update,
+            toggle_wire_routing_mode.run_if(on_event::<ToggleWireRoutingEvent>),
+        )
         .add_systems(
             Update,
             update_wire_view_signal_colors.after(propagate_signals),
         )
-        .add_systems(Update, update_wire_bbox.after(create_wire_joint));
+        .add_systems(
+            Update,
+            update_wire_bbox
+                .after(create_wire_joint)
+                .after(update_wire_routes),
+        );
         //TODO: observers or Changed<> Filter
     }
 }
@@ -115,9 +129,9 @@ fn on_select_wire(
             continue;
         };
 
-        let joint_entity = model_registry.get_model_entity(joint);
-
-        commands.entity(joint_entity).insert(Selected);
+        if let Some(joint_entity) = model_registry.try_get_model_entity(joint) {
+            commands.entity(joint_entity).insert(Selected);
+        }
     }
 }
 
@@ -178,6 +192,7 @@ pub struct WireViewBundle {
     shape_bundle: ShapeBundle,
     stroke: Stroke,
     bounding_box: BoundingBox,
+    wire_route: WireRoute,
 }
 
 impl WireViewBundle {
@@ -194,6 +209,7 @@ impl WireViewBundle {
             ),
             wire_view: WireView,
             bounding_box: BoundingBox::wire_new(Vec::new(), render_settings.wire_line_width, true),
+            wire_route: WireRoute::default(),
         }
     }
 }
@@ -205,41 +221,20 @@ impl BuildView for WireModel {
     }
 }
 
-#[allow(clippy::type_complexity)]
 pub fn update_wire_views(
-    q_wires: Query<(&mut WireNodes, &Viewable<WireModel>, Entity)>,
-    q_pins: Query<(&GlobalTransform, &PinView)>,
-    q_wire_joints: Query<(&ModelId, &Position), With<WireJointModel>>,
-    model_registry: Res<ModelRegistry>,
+    q_wires: Query<(&Viewable<WireModel>, Entity), With<WireNodes>>,
     q_cursor: Query<&Cursor>,
-    mut q_wire_views: Query<&mut Path, With<WireView>>,
+    mut q_wire_views: Query<(&mut Path, &WireRoute), With<WireView>>,
 ) {
     let cursor = get_cursor!(q_cursor);
 
-    for (wire, wire_viewable, wire_entity) in q_wires.iter() {
-        let mut wire_path = q_wire_views.get_mut(wire_viewable.view().entity()).unwrap();
-
-        //TODO: duplicate code, only update bounding box then use values from there
-        let mut points: Vec<Vec2> = wire
-            .0
-            .iter()
-            .map(|wire_node| match wire_node {
-                WireNode::Joint(joint_uuid) => {
-                    q_wire_joints
-                        .get(model_registry.get_model_entity(joint_uuid))
-                        .unwrap()
-                        .1
-                         .0
-                }
-                WireNode::Pin(pin_uuid) => q_pins
-                    .iter()
-                    .find(|(_, pin_view)| pin_view.uuid == *pin_uuid)
-                    .unwrap()
-                    .0
-                    .translation()
-                    .truncate(),
-            })
-            .collect();
+    for (wire_viewable, wire_entity) in q_wires.iter() {
+        let Ok((mut wire_path, wire_route)) = q_wire_views.get_mut(wire_viewable.view().entity())
+        else {
+            continue;
+        };
+
+        let mut points = wire_route.points.clone();
 
         //TODO: optimize by checking if dragged first otherwise only change if changed,
         //maybe get dragged wire first then update changed ones
@@ -261,41 +256,11 @@ pub fn update_wire_views(
 }
 
 //TODO: performance
-pub fn update_wire_bbox(
-    q_wires: Query<(&WireNodes, &Viewable<WireModel>)>,
-    mut q_wire_views: Query<&mut BoundingBox, With<WireView>>,
-    q_wire_joints: Query<(&ModelId, &Position), With<WireJointModel>>,
-    q_pins: Query<(&GlobalTransform, &PinView)>,
-    model_registry: Res<ModelRegistry>,
-) {
-    for (wire_nodes, wire_viewable) in q_wires.iter() {
-        //TODO: duplicate
-        let wire_points: Vec<Vec2> = wire_nodes
-            .0
-            .iter()
-            .map(|wire_node| match wire_node {
-                WireNode::Joint(joint_uuid) => {
-                    q_wire_joints
-                        .get(model_registry.get_model_entity(joint_uuid))
-                        .unwrap()
-                        .1
-                         .0
-                }
-                WireNode::Pin(pin_uuid) => q_pins
-                    .iter()
-                    .find(|(_, pin_view)| pin_view.uuid == *pin_uuid)
-                    .unwrap()
-                    .0
-                    .translation()
-                    .truncate(),
-            })
-            .collect();
-
-        let mut wire_bbox = q_wire_views.get_mut(wire_viewable.view().entity()).unwrap();
-
+pub fn update_wire_bbox(mut q_wire_views: Query<(&mut BoundingBox, &WireRoute), With<WireView>>) {
+    for (mut wire_bbox, wire_route) in q_wire_views.iter_mut() {
         match &mut wire_bbox.bounding_shape {
             BoundingShape::Wire(wire_shape) => {
-                wire_shape.points = wire_points;
+                wire_shape.points = wire_route.points.clone();
             }
             _ => panic!("invalid bounding shape for wire"),
         }
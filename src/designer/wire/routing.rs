@@ -0,0 +1,506 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use bevy::{
+    math::bounding::{Aabb2d, BoundingVolume, IntersectsVolume},
+    prelude::*,
+};
+use moonshine_view::Viewable;
+
+use crate::{
+    designer::{
+        bounding_box::{BoundingBox, BoundingShape},
+        cursor::{Cursor, CursorState},
+        devices::device::DeviceView,
+        model::ModelRegistry,
+        pin::PinView,
+        position::Position,
+        render_settings::CircuitBoardRenderingSettings,
+    },
+    get_cursor,
+};
+
+use super::{wire_joint::WireJointModel, WireModel, WireNode, WireNodes, WireView};
+
+/// Amount of grid cells the search area extends past the two points that are being connected.
+const SEARCH_PADDING_CELLS: i32 = 30;
+
+/// Extra cost for changing direction, so routes prefer few long segments over staircases.
+const TURN_COST: u32 = 5;
+
+const DIRECTIONS: [IVec2; 4] = [IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y];
+
+/// Decides how wires are laid out between their [`WireNode`]s.
+#[derive(Resource, Default, PartialEq, Eq, Clone, Copy, Debug)]
+pub enum WireRoutingMode {
+    /// Straight lines between the wire nodes.
+    #[default]
+    Direct,
+    /// Horizontal and vertical segments that are routed around devices.
+    Orthogonal,
+}
+
+/// The points a wire is actually drawn along, including automatically generated bend points.
+/// Lives on the wire view and is used for rendering, selection and highlighting.
+#[derive(Component, Default, Clone)]
+pub struct WireRoute {
+    pub points: Vec<Vec2>,
+    node_points: Vec<Vec2>,
+}
+
+pub fn toggle_wire_routing_mode(mut routing_mode: ResMut<WireRoutingMode>) {
+    *routing_mode = match *routing_mode {
+        WireRoutingMode::Direct => WireRoutingMode::Orthogonal,
+        WireRoutingMode::Orthogonal => WireRoutingMode::Direct,
+    };
+}
+
+/// Recalculates the [`WireRoute`] of every wire whose nodes moved.
+/// When devices were added, removed or moved, only wires whose route lies near one of them are
+/// rerouted, because only those might now be blocked or have a shorter way around.
+/// While devices are dragged, moved wires are drawn as straight lines and obstacles are not
+/// compared, everything that changed is routed once when the drag ends.
+#[allow(clippy::too_many_arguments)]
+pub fn update_wire_routes(
+    q_wires: Query<(&WireNodes, &Viewable<WireModel>)>,
+    mut q_wire_routes: Query<&mut WireRoute, With<WireView>>,
+    q_wire_joints: Query<&Position, With<WireJointModel>>,
+    q_pins: Query<(&GlobalTransform, &PinView)>,
+    q_device_bboxes: Query<&BoundingBox, With<DeviceView>>,
+    q_cursor: Query<&Cursor>,
+    routing_mode: Res<WireRoutingMode>,
+    render_settings: Res<CircuitBoardRenderingSettings>,
+    model_registry: Res<ModelRegistry>,
+    mut previous_obstacles: Local<Vec<(Vec2, Vec2)>>,
+) {
+    let cursor = get_cursor!(q_cursor);
+    let dragging = cursor.state == CursorState::Dragging;
+    let grid_size = render_settings.wire_routing_grid_size;
+
+    let obstacles: Vec<Aabb2d> = q_device_bboxes
+        .iter()
+        .filter_map(|bbox| match bbox.bounding_shape {
+            BoundingShape::Aabb(aabb) => {
+                Some(aabb.grow(Vec2::splat(render_settings.wire_routing_clearance)))
+            }
+            _ => None,
+        })
+        .collect();
+
+    // obstacles that were added, removed or moved since the last routing
+    let mut changed_obstacles: Vec<Aabb2d> = Vec::new();
+    if *routing_mode == WireRoutingMode::Orthogonal && !dragging {
+        let obstacle_extents: Vec<(Vec2, Vec2)> =
+            obstacles.iter().map(|o| (o.min, o.max)).collect();
+
+        changed_obstacles = obstacle_extents
+            .iter()
+            .filter(|extents| !previous_obstacles.contains(extents))
+            .chain(
+                previous_obstacles
+                    .iter()
+                    .filter(|extents| !obstacle_extents.contains(extents)),
+            )
+            .map(|&(min, max)| Aabb2d { min, max })
+            .collect();
+
+        *previous_obstacles = obstacle_extents;
+    }
+
+    for (wire_nodes, wire_viewable) in q_wires.iter() {
+        let Ok(mut wire_route) = q_wire_routes.get_mut(wire_viewable.view().entity()) else {
+            continue;
+        };
+
+        let node_points: Vec<Vec2> = wire_nodes
+            .0
+            .iter()
+            .filter_map(|wire_node| match wire_node {
+                WireNode::Joint(joint_uuid) => model_registry
+                    .try_get_model_entity(joint_uuid)
+                    .and_then(|joint_entity| q_wire_joints.get(joint_entity).ok())
+                    .map(|position| position.0),
+                WireNode::Pin(pin_uuid) => q_pins
+                    .iter()
+                    .find(|(_, pin_view)| pin_view.uuid == *pin_uuid)
+                    .map(|(transform, _)| transform.translation().truncate()),
+            })
+            .collect();
+
+        let nodes_moved = wire_route.node_points != node_points;
+        let obstacles_changed = route_bounds(&wire_route.points).is_some_and(|bounds| {
+            let bounds = bounds.grow(Vec2::splat(grid_size));
+            changed_obstacles
+                .iter()
+                .any(|obstacle| obstacle.intersects(&bounds))
+        });
+
+        if !routing_mode.is_changed() && !nodes_moved && !obstacles_changed {
+            continue;
+        }
+
+        // the node points are not stored, so the wire is routed properly once the drag ends
+        if dragging && *routing_mode == WireRoutingMode::Orthogonal {
+            wire_route.points = node_points;
+            continue;
+        }
+
+        wire_route.points = match *routing_mode {
+            WireRoutingMode::Direct => node_points.clone(),
+            WireRoutingMode::Orthogonal => route_orthogonal(&node_points, &obstacles, grid_size),
+        };
+        wire_route.node_points = node_points;
+    }
+}
+
+/// The smallest box containing all points of a route.
+fn route_bounds(points: &[Vec2]) -> Option<Aabb2d> {
+    let first_point = *points.first()?;
+
+    Some(points.iter().fold(
+        Aabb2d {
+            min: first_point,
+            max: first_point,
+        },
+        |bounds, &point| Aabb2d {
+            min: bounds.min.min(point),
+            max: bounds.max.max(point),
+        },
+    ))
+}
+
+/// Connects all points with horizontal and vertical segments that avoid the given obstacles.
+pub fn route_orthogonal(points: &[Vec2], obstacles: &[Aabb2d], grid_size: f32) -> Vec<Vec2> {
+    let Some(&first_point) = points.first() else {
+        return Vec::new();
+    };
+
+    let mut route = vec![first_point];
+
+    for window in points.windows(2) {
+        let (start, end) = (window[0], window[1]);
+
+        // Pins sit on the edge of their device, so leave the device sideways before routing.
+        let start_escape = escape_point(start, obstacles, grid_size);
+        let end_escape = escape_point(end, obstacles, grid_size);
+
+        route.push(start_escape);
+        match find_path(start_escape, end_escape, obstacles, grid_size) {
+            Some(path) => route.extend(path),
+            None => route.push(Vec2::new(end_escape.x, start_escape.y)),
+        }
+        route.push(end_escape);
+        route.push(end);
+    }
+
+    simplify_route(route)
+}
+
+/// Moves a point horizontally out of the obstacle it lies in, away from the obstacles center.
+fn escape_point(point: Vec2, obstacles: &[Aabb2d], grid_size: f32) -> Vec2 {
+    let Some(obstacle) = obstacles
+        .iter()
+        .find(|obstacle| obstacle.closest_point(point) == point)
+    else {
+        return point;
+    };
+
+    match point.x < obstacle.center().x {
+        true => Vec2::new(obstacle.min.x - grid_size, point.y),
+        false => Vec2::new(obstacle.max.x + grid_size, point.y),
+    }
+}
+
+#[derive(PartialEq, Eq)]
+struct OpenCell {
+    cost: u32,
+    estimate: u32,
+    cell: IVec2,
+    direction: usize,
+}
+
+impl Ord for OpenCell {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed so the BinaryHeap pops the cheapest cell first
+        (other.cost + other.estimate).cmp(&(self.cost + self.estimate))
+    }
+}
+
+impl PartialOrd for OpenCell {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Number of directions a cell can be entered from, the last one is used for the start cell only.
+const DIRECTION_STATES: usize = DIRECTIONS.len() + 1;
+
+/// The cells that are searched, costs and predecessors are stored per cell and direction in
+/// dense arrays, which is a lot faster than hashing every visited cell.
+struct SearchArea {
+    min: IVec2,
+    max: IVec2,
+}
+
+impl SearchArea {
+    fn width(&self) -> usize {
+        (self.max.x - self.min.x + 1) as usize
+    }
+
+    fn len(&self) -> usize {
+        self.width() * (self.max.y - self.min.y + 1) as usize * DIRECTION_STATES
+    }
+
+    fn contains(&self, cell: IVec2) -> bool {
+        cell.cmpge(self.min).all() && cell.cmple(self.max).all()
+    }
+
+    fn index(&self, cell: IVec2, direction: usize) -> usize {
+        let offset = (cell - self.min).as_uvec2();
+        (offset.y as usize * self.width() + offset.x as usize) * DIRECTION_STATES + direction
+    }
+
+    fn state(&self, index: usize) -> (IVec2, usize) {
+        let cell_index = index / DIRECTION_STATES;
+        let offset = IVec2::new(
+            (cell_index % self.width()) as i32,
+            (cell_index / self.width()) as i32,
+        );
+        (self.min + offset, index % DIRECTION_STATES)
+    }
+}
+
+/// A* search over a grid anchored at `start`.
+/// Returns the bend points between `start` and `end`, or [`None`] if no path exists in the search area.
+fn find_path(start: Vec2, end: Vec2, obstacles: &[Aabb2d], grid_size: f32) -> Option<Vec<Vec2>> {
+    let to_world = |cell: IVec2| start + cell.as_vec2() * grid_size;
+    let target = ((end - start) / grid_size).round().as_ivec2();
+
+    let search_area = SearchArea {
+        min: IVec2::ZERO.min(target) - IVec2::splat(SEARCH_PADDING_CELLS),
+        max: IVec2::ZERO.max(target) + IVec2::splat(SEARCH_PADDING_CELLS),
+    };
+
+    // only obstacles inside the search area can block a cell
+    let search_bounds = Aabb2d {
+        min: to_world(search_area.min),
+        max: to_world(search_area.max),
+    };
+    let obstacles: Vec<&Aabb2d> = obstacles
+        .iter()
+        .filter(|obstacle| obstacle.intersects(&search_bounds))
+        .collect();
+
+    let is_blocked = |cell: IVec2| {
+        let point = to_world(cell);
+        cell != IVec2::ZERO
+            && cell != target
+            && obstacles
+                .iter()
+                .any(|obstacle| obstacle.closest_point(point) == point)
+    };
+
+    let heuristic = |cell: IVec2| (target - cell).abs().element_sum() as u32;
+
+    let start_direction = DIRECTIONS.len();
+    let mut open = BinaryHeap::new();
+    let mut best_costs: Vec<u32> = vec![u32::MAX; search_area.len()];
+    let mut came_from: Vec<Option<usize>> = vec![None; search_area.len()];
+
+    open.push(OpenCell {
+        cost: 0,
+        estimate: heuristic(IVec2::ZERO),
+        cell: IVec2::ZERO,
+        direction: start_direction,
+    });
+    best_costs[search_area.index(IVec2::ZERO, start_direction)] = 0;
+
+    while let Some(OpenCell {
+        cost,
+        cell,
+        direction,
+        ..
+    }) = open.pop()
+    {
+        let index = search_area.index(cell, direction);
+
+        if cell == target {
+            let mut states = vec![(cell, direction)];
+            let mut current = index;
+            while let Some(previous) = came_from[current] {
+                states.push(search_area.state(previous));
+                current = previous;
+            }
+            states.reverse();
+
+            return Some(reconstruct_path(&states, end, to_world));
+        }
+
+        if best_costs[index] < cost {
+            continue;
+        }
+
+        for (next_direction, offset) in DIRECTIONS.iter().enumerate() {
+            let next_cell = cell + *offset;
+
+            if !search_area.contains(next_cell) || is_blocked(next_cell) {
+                continue;
+            }
+
+            let turn_cost = match direction == start_direction || direction == next_direction {
+                true => 0,
+                false => TURN_COST,
+            };
+            let next_cost = cost + 1 + turn_cost;
+
+            let next_index = search_area.index(next_cell, next_direction);
+            if best_costs[next_index] <= next_cost {
+                continue;
+            }
+
+            best_costs[next_index] = next_cost;
+            came_from[next_index] = Some(index);
+            open.push(OpenCell {
+                cost: next_cost,
+                estimate: heuristic(next_cell),
+                cell: next_cell,
+                direction: next_direction,
+            });
+        }
+    }
+
+    None
+}
+
+/// Turns the searched cells into points that end orthogonally at `end`.
+fn reconstruct_path(
+    states: &[(IVec2, usize)],
+    end: Vec2,
+    to_world: impl Fn(IVec2) -> Vec2,
+) -> Vec<Vec2> {
+    let mut points: Vec<Vec2> = states.iter().map(|&(cell, _)| to_world(cell)).collect();
+    let last = points.len() - 1;
+    let horizontal = |direction: usize| DIRECTIONS.get(direction).is_some_and(|d| d.y == 0);
+
+    // The end point is usually not aligned to the grid. Instead of adding a jog at the end, the last
+    // straight run is moved onto the end point, together with the bend before it, which only slides
+    // along the previous segment.
+    let last_direction = states[last].1;
+    let run_start = states
+        .iter()
+        .rposition(|&(_, direction)| horizontal(direction) != horizontal(last_direction))
+        .unwrap_or(0);
+
+    match (last_direction < DIRECTIONS.len(), run_start) {
+        // the path is a single straight run from the start, which can not be moved
+        (true, 0) | (false, _) => {
+            let last_point = points[last];
+            match horizontal(last_direction) {
+                true => points.push(Vec2::new(last_point.x, end.y)),
+                false => points.push(Vec2::new(end.x, last_point.y)),
+            }
+        }
+        (true, run_start) => {
+            for point in points[run_start..].iter_mut() {
+                match horizontal(last_direction) {
+                    true => point.y = end.y,
+                    false => point.x = end.x,
+                }
+            }
+        }
+    }
+
+    points
+}
+
+/// Removes duplicate points and points that lie on a straight line between their neighbours.
+fn simplify_route(route: Vec<Vec2>) -> Vec<Vec2> {
+    let mut simplified: Vec<Vec2> = Vec::with_capacity(route.len());
+
+    for point in route {
+        if simplified.last() == Some(&point) {
+            continue;
+        }
+
+        if simplified.len() >= 2 {
+            let a = simplified[simplified.len() - 2];
+            let b = simplified[simplified.len() - 1];
+            let collinear = (a.x == b.x && b.x == point.x) || (a.y == b.y && b.y == point.y);
+
+            if collinear {
+                simplified.pop();
+            }
+        }
+
+        simplified.push(point);
+    }
+
+    simplified
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID_SIZE: f32 = 10.0;
+
+    /// The path with its start and end point, as it is added to the route.
+    fn full_path(start: Vec2, end: Vec2, obstacles: &[Aabb2d]) -> Option<Vec<Vec2>> {
+        let path = find_path(start, end, obstacles, GRID_SIZE)?;
+        Some([vec![start], path, vec![end]].concat())
+    }
+
+    fn is_orthogonal(points: &[Vec2]) -> bool {
+        points
+            .windows(2)
+            .all(|segment| segment[0].x == segment[1].x || segment[0].y == segment[1].y)
+    }
+
+    #[test]
+    fn routes_around_an_obstacle() {
+        let obstacle = Aabb2d {
+            min: Vec2::new(40.0, -20.0),
+            max: Vec2::new(60.0, 20.0),
+        };
+        let points = full_path(Vec2::ZERO, Vec2::new(100.0, 0.0), &[obstacle]).unwrap();
+
+        assert!(is_orthogonal(&points));
+        for segment in points.windows(2) {
+            let segment_bounds = route_bounds(segment).unwrap();
+            assert!(!segment_bounds.intersects(&obstacle));
+        }
+    }
+
+    #[test]
+    fn finds_no_route_to_an_enclosed_end() {
+        let obstacle = Aabb2d {
+            min: Vec2::new(80.0, -20.0),
+            max: Vec2::new(120.0, 20.0),
+        };
+
+        assert_eq!(
+            find_path(Vec2::ZERO, Vec2::new(100.0, 0.0), &[obstacle], GRID_SIZE),
+            None
+        );
+    }
+
+    #[test]
+    fn ends_orthogonally_at_an_unaligned_end() {
+        // the end is between grid cells in both directions
+        let end = Vec2::new(53.0, 27.0);
+        let points = full_path(Vec2::ZERO, end, &[]).unwrap();
+
+        assert!(is_orthogonal(&points));
+        // one bend, the last run was moved onto the end instead of adding a jog
+        assert_eq!(simplify_route(points).len(), 3);
+    }
+
+    #[test]
+    fn ends_orthogonally_after_a_straight_run() {
+        let end = Vec2::new(53.0, 2.0);
+        let points = full_path(Vec2::ZERO, end, &[]).unwrap();
+
+        // the run starts at the start point, so it can not be moved and a jog is added
+        assert!(is_orthogonal(&points));
+        assert_eq!(*points.last().unwrap(), end);
+    }
+}
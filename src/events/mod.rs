@@ -14,12 +14,28 @@ impl Plugin for EventsPlugin {
             .add_event::<ToggleDebugModeEvent>()
             .add_event::<CopyEvent>()
             .add_event::<PasteEvent>()
+            .add_event::<DuplicateEvent>()
             .add_event::<SelectAllEvent>()
             .add_event::<SaveEvent>()
             .add_event::<LoadEvent>()
             .add_event::<SaveRequestEvent>()
+            .add_event::<SaveAsRequestEvent>()
+            .add_event::<OpenRecentFileEvent>()
             .add_event::<LoadRequestEvent>()
-            .add_event::<NewFileEvent>();
+            .add_event::<NewFileEvent>()
+            .add_event::<NewTabEvent>()
+            .add_event::<SwitchTabEvent>()
+            .add_event::<CloseTabEvent>()
+            .add_event::<ToggleWireRoutingEvent>()
+            .add_event::<CheckDesignEvent>()
+            .add_event::<FocusEntityEvent>()
+            .add_event::<ToggleSimulationPausedEvent>()
+            .add_event::<RestoreRecoveryEvent>()
+            .add_event::<DiscardRecoveryEvent>()
+            .add_event::<ImportLibraryRequestEvent>()
+            .add_event::<ExportLibraryRequestEvent>()
+            .add_event::<SpawnLibraryPartEvent>()
+            .add_event::<LoadMemoryImageRequestEvent>();
     }
 }
 
@@ -44,6 +60,10 @@ pub struct CopyEvent;
 #[derive(Event, Clone)]
 pub struct PasteEvent;
 
+/// Copies the selection and pastes it next to the original in one step.
+#[derive(Event, Clone)]
+pub struct DuplicateEvent;
+
 #[derive(Event, Clone)]
 pub struct SelectAllEvent;
 
@@ -72,8 +92,79 @@ impl GetFilePath for LoadEvent {
 #[derive(Event, Clone)]
 pub struct SaveRequestEvent;
 
+/// Asks for a new file to save to, even if the board already has one.
+#[derive(Event, Clone)]
+pub struct SaveAsRequestEvent;
+
 #[derive(Event, Clone)]
 pub struct LoadRequestEvent;
 
+/// Loads a file from the recent files list.
+#[derive(Event, Clone)]
+pub struct OpenRecentFileEvent {
+    pub path: PathBuf,
+}
+
 #[derive(Event, Clone)]
 pub struct NewFileEvent;
+
+/// Opens an empty board in a new tab.
+#[derive(Event, Clone)]
+pub struct NewTabEvent;
+
+#[derive(Event, Clone)]
+pub struct SwitchTabEvent {
+    pub index: usize,
+}
+
+#[derive(Event, Clone)]
+pub struct CloseTabEvent {
+    pub index: usize,
+}
+
+#[derive(Event, Clone)]
+pub struct ToggleWireRoutingEvent;
+
+#[derive(Event, Clone)]
+pub struct CheckDesignEvent;
+
+/// Selects the entity and moves the camera to the given position.
+#[derive(Event, Clone)]
+pub struct FocusEntityEvent {
+    pub entity: Entity,
+    pub position: Vec2,
+}
+
+#[derive(Event, Clone)]
+pub struct ToggleSimulationPausedEvent;
+
+/// Loads the boards from the recovery files of sessions that did not exit cleanly into new tabs.
+#[derive(Event, Clone)]
+pub struct RestoreRecoveryEvent;
+
+#[derive(Event, Clone)]
+pub struct DiscardRecoveryEvent;
+
+#[derive(Event, Clone)]
+pub struct ImportLibraryRequestEvent;
+
+/// Exports the selection as a part with the given name into a library file.
+#[derive(Event, Clone)]
+pub struct ExportLibraryRequestEvent {
+    pub part_name: String,
+}
+
+/// Spawns a part of an imported library centered on the position.
+#[derive(Event, Clone)]
+pub struct SpawnLibraryPartEvent {
+    pub library: PathBuf,
+    pub part: String,
+    pub position: Position,
+    pub init_drag: bool,
+}
+
+/// Asks for a hex, Intel HEX or binary file to load into the memory device.
+#[derive(Event, Clone)]
+pub struct LoadMemoryImageRequestEvent {
+    pub device: Entity,
+}
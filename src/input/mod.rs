@@ -1,8 +1,12 @@
 use bevy::prelude::*;
 
-use crate::events::{
-    CopyEvent, DeleteEvent, LoadRequestEvent, NewFileEvent, PasteEvent, SaveRequestEvent,
-    SelectAllEvent, ToggleDebugModeEvent,
+use crate::{
+    events::{
+        CheckDesignEvent, CopyEvent, DeleteEvent, DuplicateEvent, LoadRequestEvent, NewFileEvent,
+        NewTabEvent, PasteEvent, SaveAsRequestEvent, SaveRequestEvent, SelectAllEvent,
+        ToggleDebugModeEvent, ToggleSimulationPausedEvent, ToggleWireRoutingEvent,
+    },
+    ui::cursor_captured::IsKeyboardCaptured,
 };
 
 pub struct InputPlugin;
@@ -13,10 +17,22 @@ impl Plugin for InputPlugin {
             .register_keybinding(vec![KeyCode::Delete], DeleteEvent)
             .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyC], CopyEvent)
             .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyV], PasteEvent)
+            .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyD], DuplicateEvent)
             .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyS], SaveRequestEvent)
+            .register_keybinding(
+                vec![KeyCode::ControlLeft, KeyCode::ShiftLeft, KeyCode::KeyS],
+                SaveAsRequestEvent,
+            )
             .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyL], LoadRequestEvent)
             .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyN], NewFileEvent)
-            .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyA], SelectAllEvent);
+            .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyT], NewTabEvent)
+            .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyA], SelectAllEvent)
+            .register_keybinding(
+                vec![KeyCode::ControlLeft, KeyCode::KeyR],
+                ToggleWireRoutingEvent,
+            )
+            .register_keybinding(vec![KeyCode::ControlLeft, KeyCode::KeyK], CheckDesignEvent)
+            .register_keybinding(vec![KeyCode::Space], ToggleSimulationPausedEvent);
     }
 }
 
@@ -36,8 +52,16 @@ impl RegisterKeybinding for App {
     ) -> &mut Self {
         self.add_systems(
             Update,
-            move |input: Res<ButtonInput<KeyCode>>, event_writer: EventWriter<E>| {
-                handle_keybinding(keybinding.clone(), event_writer, input, event.clone());
+            move |input: Res<ButtonInput<KeyCode>>,
+                  keyboard_captured: Res<IsKeyboardCaptured>,
+                  event_writer: EventWriter<E>| {
+                handle_keybinding(
+                    keybinding.clone(),
+                    event_writer,
+                    input,
+                    keyboard_captured,
+                    event.clone(),
+                );
             },
         );
 
@@ -45,12 +69,26 @@ impl RegisterKeybinding for App {
     }
 }
 
+const MODIFIER_KEYS: [KeyCode; 6] = [
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+];
+
 fn handle_keybinding<E: Event>(
     keybinding: Vec<KeyCode>,
     mut event_writer: EventWriter<E>,
     input: Res<ButtonInput<KeyCode>>,
+    keyboard_captured: Res<IsKeyboardCaptured>,
     event: E,
 ) {
+    if keyboard_captured.0 {
+        return;
+    }
+
     if !keybinding.iter().all(|key| input.pressed(*key)) {
         return;
     }
@@ -59,5 +97,13 @@ fn handle_keybinding<E: Event>(
         return;
     }
 
+    // e.g. Ctrl+Shift+S should not also trigger Ctrl+S
+    if MODIFIER_KEYS
+        .iter()
+        .any(|key| input.pressed(*key) && !keybinding.contains(key))
+    {
+        return;
+    }
+
     event_writer.send(event);
 }
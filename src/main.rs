@@ -4,6 +4,7 @@ use bevy_prototype_lyon::prelude::*;
 
 mod assets;
 mod camera;
+mod cli;
 mod debug;
 mod designer;
 mod events;
@@ -12,6 +13,7 @@ mod simulation;
 mod ui;
 
 use camera::CameraPlugin;
+use cli::{run_headless_check, CliArgs, CliPlugin, USAGE};
 use debug::DebugPlugin;
 use designer::DesignerPlugins;
 
@@ -21,7 +23,24 @@ use moonshine_save::{load::LoadPlugin, save::SavePlugin};
 use simulation::SimulationPlugin;
 use ui::UIPlugin;
 
-fn main() {
+fn main() -> AppExit {
+    let cli_args = match CliArgs::parse(std::env::args().skip(1)).and_then(CliArgs::resolve_file) {
+        Ok(cli_args) => cli_args,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            return AppExit::error();
+        }
+    };
+
+    if cli_args.help {
+        println!("{}", USAGE);
+        return AppExit::Success;
+    }
+
+    if cli_args.check {
+        return run_headless_check(cli_args);
+    }
+
     let mut app = App::new();
 
     app.add_plugins(
@@ -51,7 +70,8 @@ fn main() {
         .add_plugins(InputPlugin)
         .add_plugins(SimulationPlugin)
         .add_plugins(assets::AssetPlugin)
-        .add_plugins(UIPlugin);
+        .add_plugins(UIPlugin)
+        .add_plugins(CliPlugin(cli_args));
 
-    app.run();
+    app.run()
 }
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use simulation::{apply_signals, evaluate_builtin_chips};
 
+use crate::events::ToggleSimulationPausedEvent;
+
 use self::simulation::propagate_signals;
 
 pub mod simulation;
@@ -11,7 +13,26 @@ impl Plugin for SimulationPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (evaluate_builtin_chips, propagate_signals, apply_signals).chain(),
+            (evaluate_builtin_chips, propagate_signals, apply_signals)
+                .chain()
+                .run_if(simulation_running),
+        )
+        .add_systems(
+            Update,
+            toggle_simulation_paused.run_if(on_event::<ToggleSimulationPausedEvent>),
         );
     }
 }
+
+/// The simulation follows the virtual time, so its speed and pause state also apply to clocks.
+pub fn simulation_running(time: Res<Time<Virtual>>) -> bool {
+    !time.is_paused()
+}
+
+pub fn toggle_simulation_paused(mut time: ResMut<Time<Virtual>>) {
+    if time.is_paused() {
+        time.unpause();
+    } else {
+        time.pause();
+    }
+}
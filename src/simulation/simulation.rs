@@ -1,22 +1,60 @@
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use bevy::prelude::*;
 use uuid::Uuid;
 
 use crate::designer::{
-    devices::generic_chip::GenericChip,
-    model::{ModelId, ModelRegistry},
-    pin::PinModelCollection,
+    devices::{
+        arithmetic::shift_amount_width,
+        bit_width::BitWidth,
+        drive::OutputDrive,
+        flipflop::{FlipFlopOptions, INVERTED_OUTPUT},
+        generic_chip::GenericChip,
+        memory::MemoryContents,
+        net_label::{collect_net_label_pins, NetLabel},
+        peripherals::{KeyboardBuffer, TerminalOutput, DATA_WIDTH},
+        plexers::SelectWidth,
+        random::{LfsrTaps, PseudoRandom},
+        registers::StoredValue,
+    },
+    model::ModelId,
+    pin::{PinModelCollection, PinType},
     signal::{Signal, SignalState},
     wire::{wire_joint::WireJointModel, WireModel, WireNode, WireNodes},
 };
 
 //TODO: cleanup maybe?
 /// Evaluates all builtin chips and updates their models accordingly.
+#[allow(clippy::type_complexity)]
 pub fn evaluate_builtin_chips(
-    mut q_builtin_chip_models: Query<(&GenericChip, &mut PinModelCollection)>,
+    mut q_builtin_chip_models: Query<(
+        &GenericChip,
+        &mut PinModelCollection,
+        Option<&FlipFlopOptions>,
+        Option<&SelectWidth>,
+        Option<&BitWidth>,
+        Option<&mut StoredValue>,
+        Option<&mut MemoryContents>,
+        Option<&mut PseudoRandom>,
+        Option<&LfsrTaps>,
+        Option<&mut TerminalOutput>,
+        Option<&mut KeyboardBuffer>,
+    )>,
 ) {
-    for (builtin_chip, mut pin_model_collection) in q_builtin_chip_models.iter_mut() {
+    for (
+        builtin_chip,
+        mut pin_model_collection,
+        flip_flop_options,
+        select_width,
+        bit_width,
+        mut stored_value,
+        mut memory_contents,
+        mut pseudo_random,
+        lfsr_taps,
+        mut terminal_output,
+        mut keyboard_buffer,
+    ) in q_builtin_chip_models.iter_mut()
+    {
         match builtin_chip.name.as_str() {
             "AND-2" => {
                 let next_signal = match (
@@ -32,7 +70,7 @@ pub fn evaluate_builtin_chips(
                     .signal_state
                     .set_signal(next_signal);
             }
-            "NAND-2" => {
+            "NAND-2" | "NAND-2-OD" => {
                 let next_signal = match (
                     pin_model_collection["A"].signal_state.get_signal(),
                     pin_model_collection["B"].signal_state.get_signal(),
@@ -60,7 +98,13 @@ pub fn evaluate_builtin_chips(
                     .signal_state
                     .set_signal(next_signal);
             }
-            "NOT" => {
+            "HIGH" | "PULL-UP" => pin_model_collection["Q"]
+                .signal_state
+                .set_signal(Signal::High),
+            "LOW" | "PULL-DOWN" => pin_model_collection["Q"]
+                .signal_state
+                .set_signal(Signal::Low),
+            "NOT" | "NOT-OD" => {
                 let current_signal = pin_model_collection["A"].signal_state.get_signal().clone();
                 pin_model_collection["Q"]
                     .signal_state
@@ -82,9 +126,9 @@ pub fn evaluate_builtin_chips(
                     .set_signal(next_signal);
             }
             "JK-FF" => {
-                // High-Edge triggered
-                if *pin_model_collection["C"].signal_state.get_previous_signal() != Signal::Low
-                    || *pin_model_collection["C"].signal_state.get_signal() != Signal::High
+                let options = flip_flop_options.cloned().unwrap_or_default();
+                if apply_preset_clear(&mut pin_model_collection)
+                    || !clock_triggered(&pin_model_collection, &options)
                 {
                     continue;
                 }
@@ -103,28 +147,24 @@ pub fn evaluate_builtin_chips(
                     (Signal::High, Signal::High) => current_output_signal.negate(),
                 };
 
-                pin_model_collection["Q"]
-                    .signal_state
-                    .set_signal(next_signal);
+                set_stored_signal(&mut pin_model_collection, next_signal);
             }
             "D-FF" => {
-                // High-Edge triggered
-                if *pin_model_collection["C"].signal_state.get_previous_signal() != Signal::Low
-                    || *pin_model_collection["C"].signal_state.get_signal() != Signal::High
+                let options = flip_flop_options.cloned().unwrap_or_default();
+                if apply_preset_clear(&mut pin_model_collection)
+                    || !clock_triggered(&pin_model_collection, &options)
                 {
                     continue;
                 }
 
                 let next_signal = pin_model_collection["D"].signal_state.get_signal().clone();
 
-                pin_model_collection["Q"]
-                    .signal_state
-                    .set_signal(next_signal);
+                set_stored_signal(&mut pin_model_collection, next_signal);
             }
             "T-FF" => {
-                // High-Edge triggered
-                if *pin_model_collection["C"].signal_state.get_previous_signal() != Signal::Low
-                    || *pin_model_collection["C"].signal_state.get_signal() != Signal::High
+                let options = flip_flop_options.cloned().unwrap_or_default();
+                if apply_preset_clear(&mut pin_model_collection)
+                    || !clock_triggered(&pin_model_collection, &options)
                 {
                     continue;
                 }
@@ -138,16 +178,446 @@ pub fn evaluate_builtin_chips(
                     Signal::High => current_output_signal.negate(),
                 };
 
-                pin_model_collection["Q"]
+                set_stored_signal(&mut pin_model_collection, next_signal);
+            }
+            "SR-LATCH" => {
+                let next_signal = match (
+                    pin_model_collection["S"].signal_state.get_signal(),
+                    pin_model_collection["R"].signal_state.get_signal(),
+                ) {
+                    (Signal::Low, Signal::Low) => continue,
+                    (Signal::High, Signal::Low) => Signal::High,
+                    (Signal::Low, Signal::High) => Signal::Low,
+                    // setting and resetting at once is invalid
+                    _ => Signal::Conflict,
+                };
+
+                set_stored_signal(&mut pin_model_collection, next_signal);
+            }
+            "D-LATCH" => {
+                // transparent while enabled, holds the last value otherwise
+                let next_signal = match pin_model_collection["E"].signal_state.get_signal() {
+                    Signal::Low => continue,
+                    Signal::High => pin_model_collection["D"].signal_state.get_signal().clone(),
+                    Signal::Conflict => Signal::Conflict,
+                };
+
+                set_stored_signal(&mut pin_model_collection, next_signal);
+            }
+            "MUX" => {
+                let select_width = select_width.copied().unwrap_or_default();
+
+                let next_signal = match pin_model_collection.read_bits("S", select_width.0) {
+                    Some(select) => pin_model_collection[format!("D{}", select).as_str()]
+                        .signal_state
+                        .get_signal()
+                        .clone(),
+                    None => Signal::Conflict,
+                };
+
+                pin_model_collection["Y"]
                     .signal_state
                     .set_signal(next_signal);
             }
-            _ => panic!(
-                "Tried to evaluate unknown BuiltinChip: {}",
-                builtin_chip.name
-            ),
+            "DEMUX" => {
+                let select_width = select_width.copied().unwrap_or_default();
+                let select = pin_model_collection.read_bits("S", select_width.0);
+                let input_signal = pin_model_collection["D"].signal_state.get_signal().clone();
+
+                for line in 0..select_width.num_lines() as u64 {
+                    let next_signal = match select {
+                        Some(select) if select == line => input_signal.clone(),
+                        Some(_) => Signal::Low,
+                        None => Signal::Conflict,
+                    };
+
+                    pin_model_collection[format!("Y{}", line).as_str()]
+                        .signal_state
+                        .set_signal(next_signal);
+                }
+            }
+            "DECODER" => {
+                let select_width = select_width.copied().unwrap_or_default();
+                let address = pin_model_collection.read_bits("A", select_width.0);
+
+                let one_hot = address.map(|address| 1 << address);
+                pin_model_collection.write_bits("Y", select_width.num_lines(), one_hot);
+            }
+            "PRIORITY-ENC" => {
+                let select_width = select_width.copied().unwrap_or_default();
+
+                match pin_model_collection.read_bits("D", select_width.num_lines()) {
+                    Some(0) => {
+                        pin_model_collection.write_bits("A", select_width.0, Some(0));
+                        pin_model_collection["V"]
+                            .signal_state
+                            .set_signal(Signal::Low);
+                    }
+                    Some(inputs) => {
+                        let highest_index = u64::BITS - 1 - inputs.leading_zeros();
+                        pin_model_collection.write_bits(
+                            "A",
+                            select_width.0,
+                            Some(highest_index as u64),
+                        );
+                        pin_model_collection["V"]
+                            .signal_state
+                            .set_signal(Signal::High);
+                    }
+                    None => {
+                        pin_model_collection.write_bits("A", select_width.0, None);
+                        pin_model_collection["V"]
+                            .signal_state
+                            .set_signal(Signal::Conflict);
+                    }
+                }
+            }
+            "ADDER" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+
+                let sum = match (
+                    pin_model_collection.read_bits("A", bit_width.0),
+                    pin_model_collection.read_bits("B", bit_width.0),
+                    pin_model_collection["CI"].signal_state.get_signal(),
+                ) {
+                    (Some(a), Some(b), Signal::Low) => Some(a + b),
+                    (Some(a), Some(b), Signal::High) => Some(a + b + 1),
+                    _ => None,
+                };
+
+                write_with_carry(&mut pin_model_collection, bit_width, sum);
+            }
+            "ADD-SUB" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+
+                // the borrow of a subtraction is output as the carry
+                let sum = match (
+                    pin_model_collection.read_bits("A", bit_width.0),
+                    pin_model_collection.read_bits("B", bit_width.0),
+                    pin_model_collection["SUB"].signal_state.get_signal(),
+                ) {
+                    (Some(a), Some(b), Signal::Low) => Some(a + b),
+                    (Some(a), Some(b), Signal::High) => {
+                        let borrow = (a < b) as u64;
+                        Some((borrow << bit_width.0) | (a.wrapping_sub(b) & bit_width.mask()))
+                    }
+                    _ => None,
+                };
+
+                write_with_carry(&mut pin_model_collection, bit_width, sum);
+            }
+            "COMPARATOR" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+
+                let ordering = match (
+                    pin_model_collection.read_bits("A", bit_width.0),
+                    pin_model_collection.read_bits("B", bit_width.0),
+                ) {
+                    (Some(a), Some(b)) => Some(a.cmp(&b)),
+                    _ => None,
+                };
+
+                for (label, expected) in [
+                    ("LT", std::cmp::Ordering::Less),
+                    ("EQ", std::cmp::Ordering::Equal),
+                    ("GT", std::cmp::Ordering::Greater),
+                ] {
+                    let next_signal = match ordering {
+                        Some(ordering) => Signal::from(ordering == expected),
+                        None => Signal::Conflict,
+                    };
+
+                    pin_model_collection[label]
+                        .signal_state
+                        .set_signal(next_signal);
+                }
+            }
+            "MULTIPLIER" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+
+                let product = match (
+                    pin_model_collection.read_bits("A", bit_width.0),
+                    pin_model_collection.read_bits("B", bit_width.0),
+                ) {
+                    (Some(a), Some(b)) => Some(a * b),
+                    _ => None,
+                };
+
+                pin_model_collection.write_bits("P", bit_width.0 * 2, product);
+            }
+            "SHIFTER" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+
+                let shifted = match (
+                    pin_model_collection.read_bits("D", bit_width.0),
+                    pin_model_collection.read_bits("N", shift_amount_width(bit_width)),
+                    pin_model_collection["DIR"].signal_state.get_signal(),
+                ) {
+                    // shift amounts beyond the bit width shift out everything
+                    (Some(_), Some(amount), _) if amount >= bit_width.0 as u64 => Some(0),
+                    (Some(data), Some(amount), Signal::Low) => {
+                        Some((data << amount) & bit_width.mask())
+                    }
+                    (Some(data), Some(amount), Signal::High) => Some(data >> amount),
+                    _ => None,
+                };
+
+                pin_model_collection.write_bits("Y", bit_width.0, shifted);
+            }
+            "REGISTER" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+                let Some(stored_value) = stored_value.as_mut() else {
+                    continue;
+                };
+
+                if is_high(&pin_model_collection, "CLR") {
+                    stored_value.0 = 0;
+                } else if clock_triggered(&pin_model_collection, &FlipFlopOptions::default()) {
+                    if let Some(data) = pin_model_collection.read_bits("D", bit_width.0) {
+                        stored_value.0 = data;
+                    }
+                }
+
+                pin_model_collection.write_bits("Q", bit_width.0, Some(stored_value.0));
+            }
+            "COUNTER" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+                let Some(stored_value) = stored_value.as_mut() else {
+                    continue;
+                };
+
+                let count_down = is_high(&pin_model_collection, "DIR");
+
+                if is_high(&pin_model_collection, "CLR") {
+                    stored_value.0 = 0;
+                } else if clock_triggered(&pin_model_collection, &FlipFlopOptions::default()) {
+                    if is_high(&pin_model_collection, "LD") {
+                        if let Some(data) = pin_model_collection.read_bits("D", bit_width.0) {
+                            stored_value.0 = data;
+                        }
+                    } else if count_down {
+                        stored_value.0 = stored_value.0.wrapping_sub(1) & bit_width.mask();
+                    } else {
+                        stored_value.0 = (stored_value.0 + 1) & bit_width.mask();
+                    }
+                }
+
+                let terminal_count = match count_down {
+                    false => bit_width.mask(),
+                    true => 0,
+                };
+
+                pin_model_collection.write_bits("Q", bit_width.0, Some(stored_value.0));
+                pin_model_collection["TC"]
+                    .signal_state
+                    .set_signal(Signal::from(stored_value.0 == terminal_count));
+            }
+            "SHIFT-REG" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+                let Some(stored_value) = stored_value.as_mut() else {
+                    continue;
+                };
+
+                if is_high(&pin_model_collection, "CLR") {
+                    stored_value.0 = 0;
+                } else if clock_triggered(&pin_model_collection, &FlipFlopOptions::default()) {
+                    if is_high(&pin_model_collection, "LD") {
+                        if let Some(data) = pin_model_collection.read_bits("D", bit_width.0) {
+                            stored_value.0 = data;
+                        }
+                    } else {
+                        let serial_in = is_high(&pin_model_collection, "SI") as u64;
+                        stored_value.0 = ((stored_value.0 << 1) | serial_in) & bit_width.mask();
+                    }
+                }
+
+                let serial_out = (stored_value.0 >> (bit_width.0 - 1)) & 1 == 1;
+
+                pin_model_collection.write_bits("Q", bit_width.0, Some(stored_value.0));
+                pin_model_collection["SO"]
+                    .signal_state
+                    .set_signal(Signal::from(serial_out));
+            }
+            "ROM" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+                let Some(memory_contents) = memory_contents.as_ref() else {
+                    continue;
+                };
+
+                let data = pin_model_collection
+                    .read_bits("A", memory_contents.address_width)
+                    .map(|address| memory_contents.read(address));
+
+                pin_model_collection.write_bits("D", bit_width.0, data);
+            }
+            "RAM" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+                let Some(memory_contents) = memory_contents.as_mut() else {
+                    continue;
+                };
+
+                let address = pin_model_collection.read_bits("A", memory_contents.address_width);
+
+                if is_high(&pin_model_collection, "WE")
+                    && clock_triggered(&pin_model_collection, &FlipFlopOptions::default())
+                {
+                    if let (Some(address), Some(data)) =
+                        (address, pin_model_collection.read_bits("DI", bit_width.0))
+                    {
+                        memory_contents.write(address, data);
+                    }
+                }
+
+                let data = address.map(|address| memory_contents.read(address));
+                pin_model_collection.write_bits("DO", bit_width.0, data);
+            }
+            "RANDOM" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+                let Some(pseudo_random) = pseudo_random.as_mut() else {
+                    continue;
+                };
+
+                if is_high(&pin_model_collection, "RST") {
+                    pseudo_random.restart();
+                } else if clock_triggered(&pin_model_collection, &FlipFlopOptions::default()) {
+                    pseudo_random.advance();
+                }
+
+                let value = pseudo_random.value() & bit_width.mask();
+                pin_model_collection.write_bits("Q", bit_width.0, Some(value));
+            }
+            "LFSR" => {
+                let bit_width = bit_width.copied().unwrap_or_default();
+                let taps = lfsr_taps.copied().unwrap_or_default().resolve(bit_width);
+                let Some(pseudo_random) = pseudo_random.as_mut() else {
+                    continue;
+                };
+
+                if is_high(&pin_model_collection, "RST") {
+                    pseudo_random.restart();
+                } else if clock_triggered(&pin_model_collection, &FlipFlopOptions::default()) {
+                    let feedback = (pseudo_random.state & taps).count_ones() as u64 & 1;
+                    pseudo_random.state =
+                        ((pseudo_random.state << 1) | feedback) & bit_width.mask();
+                }
+
+                let state = pseudo_random.state & bit_width.mask();
+                pin_model_collection.write_bits("Q", bit_width.0, Some(state));
+            }
+            "TERMINAL" => {
+                let Some(terminal_output) = terminal_output.as_mut() else {
+                    continue;
+                };
+
+                if is_high(&pin_model_collection, "CLR") {
+                    if !terminal_output.text.is_empty() {
+                        terminal_output.text.clear();
+                    }
+                } else if is_high(&pin_model_collection, "WE")
+                    && clock_triggered(&pin_model_collection, &FlipFlopOptions::default())
+                {
+                    if let Some(byte) = pin_model_collection.read_bits("D", DATA_WIDTH) {
+                        terminal_output.write(byte as u8);
+                    }
+                }
+            }
+            "KEYBOARD" => {
+                let Some(keyboard_buffer) = keyboard_buffer.as_mut() else {
+                    continue;
+                };
+
+                if is_high(&pin_model_collection, "CLR") {
+                    if !keyboard_buffer.characters.is_empty() {
+                        keyboard_buffer.characters.clear();
+                    }
+                } else if is_high(&pin_model_collection, "RD")
+                    && clock_triggered(&pin_model_collection, &FlipFlopOptions::default())
+                    && !keyboard_buffer.characters.is_empty()
+                {
+                    keyboard_buffer.characters.remove(0);
+                }
+
+                let character = keyboard_buffer.characters.first().copied();
+                pin_model_collection["AV"]
+                    .signal_state
+                    .set_signal(Signal::from(character.is_some()));
+                pin_model_collection.write_bits(
+                    "D",
+                    DATA_WIDTH,
+                    Some(character.unwrap_or_default() as u64),
+                );
+            }
+            // unknown chips are removed when loading, so this only happens for chips without logic
+            _ => {}
+        }
+    }
+}
+
+/// Checks for the edge on `C` the flip-flop is triggered by, while `EN` is high if it exists.
+fn clock_triggered(pin_model_collection: &PinModelCollection, options: &FlipFlopOptions) -> bool {
+    if let Some(enable_pin) = pin_model_collection.get_by_label("EN") {
+        if *enable_pin.signal_state.get_signal() != Signal::High {
+            return false;
         }
     }
+
+    let (from, to) = match options.falling_edge {
+        false => (Signal::Low, Signal::High),
+        true => (Signal::High, Signal::Low),
+    };
+
+    let clock = &pin_model_collection["C"].signal_state;
+    *clock.get_previous_signal() == from && *clock.get_signal() == to
+}
+
+/// Checks if the pin exists and is high.
+fn is_high(pin_model_collection: &PinModelCollection, label: &str) -> bool {
+    pin_model_collection
+        .get_by_label(label)
+        .is_some_and(|pin_model| *pin_model.signal_state.get_signal() == Signal::High)
+}
+
+/// Sets or clears the flip-flop while `PRE` or `CLR` is high, regardless of the clock.
+/// Returns true if the stored value was overridden.
+fn apply_preset_clear(pin_model_collection: &mut PinModelCollection) -> bool {
+    let next_signal = match (
+        is_high(pin_model_collection, "PRE"),
+        is_high(pin_model_collection, "CLR"),
+    ) {
+        (false, false) => return false,
+        (true, false) => Signal::High,
+        (false, true) => Signal::Low,
+        (true, true) => Signal::Conflict,
+    };
+
+    set_stored_signal(pin_model_collection, next_signal);
+    true
+}
+
+/// Writes the lower bits of the sum to `S*` and the bit above them to the carry output `CO`.
+fn write_with_carry(
+    pin_model_collection: &mut PinModelCollection,
+    bit_width: BitWidth,
+    sum: Option<u64>,
+) {
+    pin_model_collection.write_bits("S", bit_width.0, sum);
+
+    let carry = match sum {
+        Some(sum) => Signal::from(sum >> bit_width.0 != 0),
+        None => Signal::Conflict,
+    };
+    pin_model_collection["CO"].signal_state.set_signal(carry);
+}
+
+/// Sets `Q` of a flip-flop or latch and its inverted output.
+fn set_stored_signal(pin_model_collection: &mut PinModelCollection, signal: Signal) {
+    if pin_model_collection.get_by_label(INVERTED_OUTPUT).is_some() {
+        pin_model_collection[INVERTED_OUTPUT]
+            .signal_state
+            .set_signal(signal.clone().negate());
+    }
+
+    pin_model_collection["Q"].signal_state.set_signal(signal);
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -157,103 +627,191 @@ enum SignalNode {
     WireJoint(Uuid),
 }
 
-/// Propagates signals starting from all output pins using BFS.
-/// TODO: If different signals stack on a node, high will always be preferred.
-/// This should probably result in a conflict signal state.
-/// TODO: Optimize by only queueing changed output pins.
+/// How strongly a pin drives the net it is connected to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Drive {
+    None,
+    Weak,
+    Full,
+}
+
+/// Signal of a net that no output drives, it reads like an unconnected input.
+const UNDRIVEN_SIGNAL: Signal = Signal::Low;
+
+/// Resolves the signal of every net and pushes it to all wires, joints and pins of the net.
+/// Pins of net labels with the same name are treated as if they were connected by a wire.
+/// Outputs that do not always drive their net with full strength keep their own signal.
+/// TODO: Optimize by only resolving nets with changed outputs.
 #[allow(clippy::type_complexity)]
 pub fn propagate_signals(
     mut q_wires: Query<(&WireNodes, &mut SignalState, Entity)>,
-    mut q_pin_model_collections: Query<&mut PinModelCollection>,
+    mut q_pin_model_collections: Query<(&mut PinModelCollection, Option<&OutputDrive>)>,
     mut q_wire_joints: Query<
         (&ModelId, &mut SignalState),
         (With<WireJointModel>, Without<WireNodes>),
     >,
-    model_registry: Res<ModelRegistry>,
+    q_net_labels: Query<(Entity, &NetLabel)>,
 ) {
-    let mut visited: HashSet<SignalNode> = HashSet::new();
-    let mut queue: VecDeque<SignalNode> = q_pin_model_collections
-        .iter()
-        .flat_map(|pin_model_collection| pin_model_collection.iter_outputs())
-        .map(|pin_model| SignalNode::Pin(pin_model.uuid))
-        .collect();
+    let net_label_pins =
+        collect_net_label_pins(q_net_labels.iter().filter_map(|(entity, net_label)| {
+            q_pin_model_collections
+                .get(entity)
+                .ok()
+                .map(|(pin_model_collection, _)| (net_label, pin_model_collection))
+        }));
 
-    while let Some(node) = queue.pop_front() {
-        match &node {
-            SignalNode::Pin(pin_uuid) => {
-                let pin_model = PinModelCollection::find_in_collections(
-                    *pin_uuid,
-                    q_pin_model_collections.iter(),
-                )
-                .unwrap();
-
-                for (wire_nodes, mut wire_signal_state, wire_entity) in q_wires.iter_mut() {
-                    if !wire_nodes.0.iter().any(|node| matches!(node, WireNode::Pin(wire_node_pin_uuid) if wire_node_pin_uuid == pin_uuid)) {
-                        continue;
-                    }
+    let mut neighbours: HashMap<SignalNode, Vec<SignalNode>> = HashMap::new();
+    let mut connect = |a: SignalNode, b: SignalNode| {
+        neighbours.entry(a.clone()).or_default().push(b.clone());
+        neighbours.entry(b).or_default().push(a);
+    };
 
-                    wire_signal_state
-                        .push_signal(pin_model.signal_state.get_latest_signal().clone());
+    for (wire_nodes, _, wire_entity) in q_wires.iter() {
+        for wire_node in wire_nodes.0.iter() {
+            let node = match wire_node {
+                WireNode::Pin(pin_uuid) => SignalNode::Pin(*pin_uuid),
+                WireNode::Joint(joint_uuid) => SignalNode::WireJoint(*joint_uuid),
+            };
+            connect(SignalNode::Wire(wire_entity), node);
+        }
+    }
 
-                    enqueue_if_new(&mut queue, &visited, SignalNode::Wire(wire_entity));
-                }
+    // net labels with the same name share their signal
+    let mut first_net_pins: HashMap<&String, Uuid> = HashMap::new();
+    for (pin_uuid, net_name) in net_label_pins.iter() {
+        match first_net_pins.get(net_name) {
+            Some(first_pin_uuid) => {
+                connect(SignalNode::Pin(*first_pin_uuid), SignalNode::Pin(*pin_uuid))
+            }
+            None => {
+                first_net_pins.insert(net_name, *pin_uuid);
             }
-            SignalNode::WireJoint(joint_uuid) => {
-                let wire_joint_signal_state = q_wire_joints
-                    .get(model_registry.get_model_entity(joint_uuid))
-                    .unwrap()
-                    .1;
+        }
+    }
 
-                for (wire_nodes, mut wire_signal_state, wire_entity) in q_wires.iter_mut() {
-                    if !wire_nodes.0.iter().any(|node| matches!(node, WireNode::Joint(wire_node_joint_entity) if wire_node_joint_entity == joint_uuid)) {
-                        continue;
-                    }
+    let mut pin_drives: HashMap<Uuid, (Drive, Signal)> = HashMap::new();
+    for (pin_model_collection, output_drive) in q_pin_model_collections.iter() {
+        for pin_model in pin_model_collection.iter() {
+            let signal = pin_model.signal_state.get_latest_signal().clone();
+            let drive = match pin_model.pin_type {
+                PinType::Input => Drive::None,
+                PinType::Output => output_drive_strength(output_drive, &signal),
+            };
+            pin_drives.insert(pin_model.uuid, (drive, signal));
+        }
+    }
 
-                    wire_signal_state
-                        .push_signal(wire_joint_signal_state.get_latest_signal().clone());
+    let nodes = pin_drives
+        .keys()
+        .map(|pin_uuid| SignalNode::Pin(*pin_uuid))
+        .chain(
+            q_wires
+                .iter()
+                .map(|(_, _, entity)| SignalNode::Wire(entity)),
+        )
+        .chain(
+            q_wire_joints
+                .iter()
+                .map(|(model_id, _)| SignalNode::WireJoint(model_id.0)),
+        );
 
-                    enqueue_if_new(&mut queue, &visited, SignalNode::Wire(wire_entity));
-                }
-            }
-            SignalNode::Wire(wire_entity) => {
-                let (wire_nodes, wire_signal_state, _) = q_wires.get(*wire_entity).unwrap();
+    let mut net_signals: HashMap<SignalNode, Signal> = HashMap::new();
+    for node in nodes {
+        if net_signals.contains_key(&node) {
+            continue;
+        }
 
-                for wire_node in wire_nodes.0.iter() {
-                    match wire_node {
-                        WireNode::Joint(joint_uuid) => {
-                            let mut wire_joint_signal_state = q_wire_joints
-                                .get_mut(model_registry.get_model_entity(joint_uuid))
-                                .unwrap()
-                                .1;
+        let net = collect_net(node, &neighbours);
+        let signal = resolve_net(net.iter().filter_map(|node| match node {
+            SignalNode::Pin(pin_uuid) => pin_drives.get(pin_uuid),
+            _ => None,
+        }));
 
-                            wire_joint_signal_state
-                                .push_signal(wire_signal_state.get_latest_signal().clone());
+        for node in net {
+            net_signals.insert(node, signal.clone());
+        }
+    }
 
-                            queue.push_back(SignalNode::WireJoint(*joint_uuid));
-                        }
-                        WireNode::Pin(pin_uuid) => {
-                            PinModelCollection::pin_model_scope(
-                                q_pin_model_collections.iter_mut(),
-                                *pin_uuid,
-                                |pin_model| {
-                                    pin_model
-                                        .signal_state
-                                        .push_signal(wire_signal_state.get_latest_signal().clone());
-
-                                    enqueue_if_new(
-                                        &mut queue,
-                                        &visited,
-                                        SignalNode::Pin(*pin_uuid),
-                                    );
-                                },
-                            );
-                        }
-                    }
-                }
+    for (_, mut wire_signal_state, wire_entity) in q_wires.iter_mut() {
+        if let Some(signal) = net_signals.get(&SignalNode::Wire(wire_entity)) {
+            wire_signal_state.push_signal(signal.clone());
+        }
+    }
+
+    for (model_id, mut wire_joint_signal_state) in q_wire_joints.iter_mut() {
+        if let Some(signal) = net_signals.get(&SignalNode::WireJoint(model_id.0)) {
+            wire_joint_signal_state.push_signal(signal.clone());
+        }
+    }
+
+    for (mut pin_model_collection, output_drive) in q_pin_model_collections.iter_mut() {
+        for pin_model in pin_model_collection.iter_mut() {
+            if output_drive.is_some() && pin_model.pin_type == PinType::Output {
+                continue;
+            }
+
+            if let Some(signal) = net_signals.get(&SignalNode::Pin(pin_model.uuid)) {
+                pin_model.signal_state.push_signal(signal.clone());
             }
         }
+    }
+}
+
+/// How strongly an output drives its net while it outputs the given signal.
+/// Open-drain outputs only pull their net Low and let go of it otherwise.
+fn output_drive_strength(output_drive: Option<&OutputDrive>, signal: &Signal) -> Drive {
+    match (output_drive, signal) {
+        (None, _) => Drive::Full,
+        (Some(OutputDrive::Weak), _) => Drive::Weak,
+        (Some(OutputDrive::OpenDrain), Signal::High) => Drive::None,
+        (Some(OutputDrive::OpenDrain), _) => Drive::Full,
+    }
+}
+
+/// Collects all nodes that are connected to the given node using BFS.
+fn collect_net(
+    node: SignalNode,
+    neighbours: &HashMap<SignalNode, Vec<SignalNode>>,
+) -> HashSet<SignalNode> {
+    let mut net: HashSet<SignalNode> = HashSet::new();
+    let mut queue = VecDeque::from([node]);
+
+    while let Some(node) = queue.pop_front() {
+        if net.contains(&node) {
+            continue;
+        }
 
-        visited.insert(node);
+        if let Some(node_neighbours) = neighbours.get(&node) {
+            queue.extend(node_neighbours.iter().cloned());
+        }
+
+        net.insert(node);
+    }
+
+    net
+}
+
+/// Resolves the signal of a net from the drives and signals of its pins.
+/// Only the strongest drivers count, if they disagree the net is in conflict.
+fn resolve_net<'a>(pin_drives: impl Iterator<Item = &'a (Drive, Signal)>) -> Signal {
+    let mut strongest_drive = Drive::None;
+    let mut signals: Vec<&Signal> = Vec::new();
+
+    for (drive, signal) in pin_drives {
+        if *drive > strongest_drive {
+            strongest_drive = *drive;
+            signals.clear();
+        }
+
+        if *drive == strongest_drive && *drive != Drive::None {
+            signals.push(signal);
+        }
+    }
+
+    match signals.first() {
+        None => UNDRIVEN_SIGNAL,
+        Some(first) if signals.iter().all(|signal| signal == first) => (*first).clone(),
+        Some(_) => Signal::Conflict,
     }
 }
 
@@ -278,13 +836,480 @@ pub fn apply_signals(
     }
 }
 
-//TODO: improve or comment
-fn enqueue_if_new(
-    queue: &mut VecDeque<SignalNode>,
-    visited: &HashSet<SignalNode>,
-    node: SignalNode,
-) {
-    if !visited.contains(&node) {
-        queue.push_back(node);
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{
+        designer::{
+            devices::{device::DeviceSerializers, register_devices},
+            model::ModelRegistry,
+            position::Position,
+            save_management::format::{DeviceProperties, PropertyValue},
+        },
+        events::SpawnDeviceEvent,
+    };
+
+    /// All devices are registered, so they can be spawned and evaluated without views or wires.
+    fn device_app() -> App {
+        let mut app = App::new();
+        app.add_event::<SpawnDeviceEvent>()
+            .init_resource::<ModelRegistry>();
+        register_devices(&mut app);
+        app
+    }
+
+    /// Spawns a device and applies the properties like the properties window does.
+    fn spawn_device(world: &mut World, device_id: &str, properties: &[(&str, i64)]) -> Entity {
+        let serializer = &world.resource::<DeviceSerializers>().0[device_id];
+        let (spawn, load_properties) = (serializer.spawn, serializer.load_properties);
+
+        let mut device_properties = DeviceProperties::default();
+        for (name, value) in properties {
+            device_properties.set(name, PropertyValue::Int(*value));
+        }
+
+        let device_entity = spawn(world, Position(Vec2::ZERO));
+        load_properties(&mut world.entity_mut(device_entity), &device_properties);
+        device_entity
+    }
+
+    fn pins(world: &mut World, device_entity: Entity) -> Mut<PinModelCollection> {
+        world.get_mut::<PinModelCollection>(device_entity).unwrap()
+    }
+
+    fn set_pin(world: &mut World, device_entity: Entity, label: &str, signal: Signal) {
+        pins(world, device_entity)[label]
+            .signal_state
+            .set_signal(signal);
+    }
+
+    fn pin_signal(world: &mut World, device_entity: Entity, label: &str) -> Signal {
+        pins(world, device_entity)[label]
+            .signal_state
+            .get_signal()
+            .clone()
+    }
+
+    fn evaluate(world: &mut World) {
+        world.run_system_once(evaluate_builtin_chips).unwrap();
+    }
+
+    /// Gives the device a rising edge on `C` and evaluates it.
+    fn clock_tick(world: &mut World, device_entity: Entity) {
+        set_pin(world, device_entity, "C", Signal::Low);
+        set_pin(world, device_entity, "C", Signal::High);
+        evaluate(world);
+
+        // ends the edge, so the next evaluation does not trigger again
+        set_pin(world, device_entity, "C", Signal::High);
+    }
+
+    #[test]
+    fn multiplexer_connects_the_selected_input() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let mux = spawn_device(world, "MUX", &[("select_width", 2)]);
+
+        pins(world, mux).write_bits("D", 4, Some(0b0100));
+        pins(world, mux).write_bits("S", 2, Some(2));
+        evaluate(world);
+        assert_eq!(pin_signal(world, mux, "Y"), Signal::High);
+
+        pins(world, mux).write_bits("S", 2, Some(3));
+        evaluate(world);
+        assert_eq!(pin_signal(world, mux, "Y"), Signal::Low);
+
+        set_pin(world, mux, "S1", Signal::Conflict);
+        evaluate(world);
+        assert_eq!(pin_signal(world, mux, "Y"), Signal::Conflict);
+    }
+
+    #[test]
+    fn demultiplexer_drives_the_selected_output() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let demux = spawn_device(world, "DEMUX", &[("select_width", 2)]);
+
+        set_pin(world, demux, "D", Signal::High);
+        pins(world, demux).write_bits("S", 2, Some(3));
+        evaluate(world);
+        assert_eq!(pins(world, demux).read_bits("Y", 4), Some(0b1000));
+
+        set_pin(world, demux, "S0", Signal::Conflict);
+        evaluate(world);
+        assert_eq!(pins(world, demux).read_bits("Y", 4), None);
+    }
+
+    #[test]
+    fn decoder_outputs_one_hot() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let decoder = spawn_device(world, "DECODER", &[("select_width", 3)]);
+
+        pins(world, decoder).write_bits("A", 3, Some(5));
+        evaluate(world);
+        assert_eq!(pins(world, decoder).read_bits("Y", 8), Some(0b0010_0000));
+
+        set_pin(world, decoder, "A2", Signal::Conflict);
+        evaluate(world);
+        assert_eq!(pin_signal(world, decoder, "Y0"), Signal::Conflict);
+    }
+
+    #[test]
+    fn priority_encoder_outputs_the_highest_input() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let encoder = spawn_device(world, "PRIORITY-ENC", &[("select_width", 2)]);
+
+        pins(world, encoder).write_bits("D", 4, Some(0b0110));
+        evaluate(world);
+        assert_eq!(pins(world, encoder).read_bits("A", 2), Some(2));
+        assert_eq!(pin_signal(world, encoder, "V"), Signal::High);
+
+        pins(world, encoder).write_bits("D", 4, Some(0));
+        evaluate(world);
+        assert_eq!(pins(world, encoder).read_bits("A", 2), Some(0));
+        assert_eq!(pin_signal(world, encoder, "V"), Signal::Low);
+
+        set_pin(world, encoder, "D3", Signal::Conflict);
+        evaluate(world);
+        assert_eq!(pins(world, encoder).read_bits("A", 2), None);
+        assert_eq!(pin_signal(world, encoder, "V"), Signal::Conflict);
+    }
+
+    #[test]
+    fn adder_carries_at_the_maximum_bit_width() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let adder = spawn_device(world, "ADDER", &[("bit_width", 8)]);
+
+        pins(world, adder).write_bits("A", 8, Some(200));
+        pins(world, adder).write_bits("B", 8, Some(100));
+        set_pin(world, adder, "CI", Signal::High);
+        evaluate(world);
+        assert_eq!(pins(world, adder).read_bits("S", 8), Some(301 & 0xff));
+        assert_eq!(pin_signal(world, adder, "CO"), Signal::High);
+
+        pins(world, adder).write_bits("A", 8, Some(255));
+        pins(world, adder).write_bits("B", 8, Some(0));
+        set_pin(world, adder, "CI", Signal::Low);
+        evaluate(world);
+        assert_eq!(pins(world, adder).read_bits("S", 8), Some(255));
+        assert_eq!(pin_signal(world, adder, "CO"), Signal::Low);
+
+        set_pin(world, adder, "CI", Signal::Conflict);
+        evaluate(world);
+        assert_eq!(pins(world, adder).read_bits("S", 8), None);
+        assert_eq!(pin_signal(world, adder, "CO"), Signal::Conflict);
+    }
+
+    #[test]
+    fn adder_subtractor_outputs_the_borrow_as_carry() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let adder_subtractor = spawn_device(world, "ADD-SUB", &[("bit_width", 8)]);
+
+        set_pin(world, adder_subtractor, "SUB", Signal::High);
+        pins(world, adder_subtractor).write_bits("A", 8, Some(3));
+        pins(world, adder_subtractor).write_bits("B", 8, Some(5));
+        evaluate(world);
+        assert_eq!(pins(world, adder_subtractor).read_bits("S", 8), Some(254));
+        assert_eq!(pin_signal(world, adder_subtractor, "CO"), Signal::High);
+
+        pins(world, adder_subtractor).write_bits("A", 8, Some(5));
+        pins(world, adder_subtractor).write_bits("B", 8, Some(3));
+        evaluate(world);
+        assert_eq!(pins(world, adder_subtractor).read_bits("S", 8), Some(2));
+        assert_eq!(pin_signal(world, adder_subtractor, "CO"), Signal::Low);
+
+        set_pin(world, adder_subtractor, "SUB", Signal::Low);
+        pins(world, adder_subtractor).write_bits("A", 8, Some(255));
+        pins(world, adder_subtractor).write_bits("B", 8, Some(1));
+        evaluate(world);
+        assert_eq!(pins(world, adder_subtractor).read_bits("S", 8), Some(0));
+        assert_eq!(pin_signal(world, adder_subtractor, "CO"), Signal::High);
+
+        set_pin(world, adder_subtractor, "B7", Signal::Conflict);
+        evaluate(world);
+        assert_eq!(pins(world, adder_subtractor).read_bits("S", 8), None);
+        assert_eq!(pin_signal(world, adder_subtractor, "CO"), Signal::Conflict);
+    }
+
+    #[test]
+    fn comparator_compares_unsigned_values() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let comparator = spawn_device(world, "COMPARATOR", &[("bit_width", 8)]);
+
+        pins(world, comparator).write_bits("A", 8, Some(7));
+        pins(world, comparator).write_bits("B", 8, Some(200));
+        evaluate(world);
+        assert_eq!(pin_signal(world, comparator, "LT"), Signal::High);
+        assert_eq!(pin_signal(world, comparator, "EQ"), Signal::Low);
+        assert_eq!(pin_signal(world, comparator, "GT"), Signal::Low);
+
+        set_pin(world, comparator, "A0", Signal::Conflict);
+        evaluate(world);
+        for label in ["LT", "EQ", "GT"] {
+            assert_eq!(pin_signal(world, comparator, label), Signal::Conflict);
+        }
+    }
+
+    #[test]
+    fn multiplier_outputs_the_full_product() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let multiplier = spawn_device(world, "MULTIPLIER", &[("bit_width", 8)]);
+
+        pins(world, multiplier).write_bits("A", 8, Some(255));
+        pins(world, multiplier).write_bits("B", 8, Some(255));
+        evaluate(world);
+        assert_eq!(pins(world, multiplier).read_bits("P", 16), Some(255 * 255));
+
+        set_pin(world, multiplier, "B0", Signal::Conflict);
+        evaluate(world);
+        assert_eq!(pins(world, multiplier).read_bits("P", 16), None);
+    }
+
+    #[test]
+    fn shifter_shifts_out_of_the_bit_width() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let shifter = spawn_device(world, "SHIFTER", &[("bit_width", 8)]);
+
+        pins(world, shifter).write_bits("D", 8, Some(0b1000_0001));
+        pins(world, shifter).write_bits("N", 3, Some(1));
+        set_pin(world, shifter, "DIR", Signal::Low);
+        evaluate(world);
+        assert_eq!(pins(world, shifter).read_bits("Y", 8), Some(0b0000_0010));
+
+        set_pin(world, shifter, "DIR", Signal::High);
+        evaluate(world);
+        assert_eq!(pins(world, shifter).read_bits("Y", 8), Some(0b0100_0000));
+
+        set_pin(world, shifter, "DIR", Signal::Conflict);
+        evaluate(world);
+        assert_eq!(pins(world, shifter).read_bits("Y", 8), None);
+    }
+
+    #[test]
+    fn register_stores_only_while_enabled() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let register = spawn_device(world, "REGISTER", &[("bit_width", 8)]);
+
+        pins(world, register).write_bits("D", 8, Some(0xab));
+        clock_tick(world, register);
+        assert_eq!(pins(world, register).read_bits("Q", 8), Some(0));
+
+        set_pin(world, register, "EN", Signal::High);
+        evaluate(world);
+        assert_eq!(pins(world, register).read_bits("Q", 8), Some(0));
+
+        clock_tick(world, register);
+        assert_eq!(pins(world, register).read_bits("Q", 8), Some(0xab));
+    }
+
+    #[test]
+    fn clear_takes_priority_over_the_clock() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let register = spawn_device(world, "REGISTER", &[("bit_width", 8), ("value", 0xab)]);
+
+        set_pin(world, register, "EN", Signal::High);
+        set_pin(world, register, "CLR", Signal::High);
+        pins(world, register).write_bits("D", 8, Some(0x12));
+        clock_tick(world, register);
+        assert_eq!(pins(world, register).read_bits("Q", 8), Some(0));
+
+        let counter = spawn_device(world, "COUNTER", &[("value", 5)]);
+        set_pin(world, counter, "EN", Signal::High);
+        set_pin(world, counter, "CLR", Signal::High);
+        clock_tick(world, counter);
+        assert_eq!(pins(world, counter).read_bits("Q", 4), Some(0));
+    }
+
+    #[test]
+    fn counter_wraps_in_both_directions() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let counter = spawn_device(world, "COUNTER", &[("bit_width", 4), ("value", 14)]);
+        set_pin(world, counter, "EN", Signal::High);
+
+        clock_tick(world, counter);
+        assert_eq!(pins(world, counter).read_bits("Q", 4), Some(15));
+        assert_eq!(pin_signal(world, counter, "TC"), Signal::High);
+
+        clock_tick(world, counter);
+        assert_eq!(pins(world, counter).read_bits("Q", 4), Some(0));
+        assert_eq!(pin_signal(world, counter, "TC"), Signal::Low);
+
+        // the terminal count is 0 when counting down
+        set_pin(world, counter, "DIR", Signal::High);
+        evaluate(world);
+        assert_eq!(pin_signal(world, counter, "TC"), Signal::High);
+
+        clock_tick(world, counter);
+        assert_eq!(pins(world, counter).read_bits("Q", 4), Some(15));
+        assert_eq!(pin_signal(world, counter, "TC"), Signal::Low);
+    }
+
+    #[test]
+    fn shift_register_outputs_the_highest_bit_on_so() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let shift_register = spawn_device(world, "SHIFT-REG", &[("bit_width", 4)]);
+        set_pin(world, shift_register, "EN", Signal::High);
+        set_pin(world, shift_register, "SI", Signal::High);
+
+        clock_tick(world, shift_register);
+        assert_eq!(pins(world, shift_register).read_bits("Q", 4), Some(0b0001));
+        assert_eq!(pin_signal(world, shift_register, "SO"), Signal::Low);
+
+        for _ in 0..3 {
+            clock_tick(world, shift_register);
+        }
+        assert_eq!(pins(world, shift_register).read_bits("Q", 4), Some(0b1111));
+        assert_eq!(pin_signal(world, shift_register, "SO"), Signal::High);
+
+        set_pin(world, shift_register, "LD", Signal::High);
+        pins(world, shift_register).write_bits("D", 4, Some(0b0101));
+        clock_tick(world, shift_register);
+        assert_eq!(pins(world, shift_register).read_bits("Q", 4), Some(0b0101));
+        assert_eq!(pin_signal(world, shift_register, "SO"), Signal::Low);
+    }
+
+    #[test]
+    fn lfsr_repeats_its_maximum_length_sequence_and_restarts() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let lfsr = spawn_device(world, "LFSR", &[("bit_width", 4), ("seed", 1)]);
+        set_pin(world, lfsr, "EN", Signal::High);
+        evaluate(world);
+
+        let sequence = [1, 2, 4, 9, 3, 6, 13, 10, 5, 11, 7, 15, 14, 12, 8, 1];
+        for expected in sequence {
+            assert_eq!(pins(world, lfsr).read_bits("Q", 4), Some(expected));
+            clock_tick(world, lfsr);
+        }
+
+        set_pin(world, lfsr, "RST", Signal::High);
+        clock_tick(world, lfsr);
+        assert_eq!(pins(world, lfsr).read_bits("Q", 4), Some(1));
+
+        set_pin(world, lfsr, "RST", Signal::Low);
+        clock_tick(world, lfsr);
+        assert_eq!(pins(world, lfsr).read_bits("Q", 4), Some(2));
+    }
+
+    #[test]
+    fn lfsr_has_maximum_length_at_every_bit_width() {
+        let mut app = device_app();
+        let world = app.world_mut();
+
+        for bit_width in 1..=8 {
+            let lfsr = spawn_device(world, "LFSR", &[("bit_width", bit_width), ("seed", 1)]);
+            set_pin(world, lfsr, "EN", Signal::High);
+
+            let mut states = HashSet::new();
+            loop {
+                clock_tick(world, lfsr);
+                let state = pins(world, lfsr).read_bits("Q", bit_width as u32).unwrap();
+                if !states.insert(state) {
+                    break;
+                }
+            }
+
+            assert_eq!(states.len(), (1 << bit_width) - 1);
+            assert!(!states.contains(&0));
+        }
+    }
+
+    #[test]
+    fn lfsr_replaces_seeds_that_lock_the_register() {
+        let mut app = device_app();
+        let world = app.world_mut();
+        let lfsr = spawn_device(world, "LFSR", &[("bit_width", 4), ("seed", 0b1_0000)]);
+        set_pin(world, lfsr, "EN", Signal::High);
+
+        evaluate(world);
+        assert_eq!(pins(world, lfsr).read_bits("Q", 4), Some(1));
+
+        clock_tick(world, lfsr);
+        assert_eq!(pins(world, lfsr).read_bits("Q", 4), Some(2));
+    }
+
+    fn resolve(pin_drives: &[(Drive, Signal)]) -> Signal {
+        resolve_net(pin_drives.iter())
+    }
+
+    #[test]
+    fn full_drivers_override_weak_drivers() {
+        assert_eq!(
+            resolve(&[(Drive::Weak, Signal::High), (Drive::Full, Signal::Low)]),
+            Signal::Low
+        );
+        assert_eq!(
+            resolve(&[(Drive::Weak, Signal::Low), (Drive::None, Signal::High)]),
+            Signal::Low
+        );
+    }
+
+    #[test]
+    fn disagreeing_drivers_of_the_same_strength_conflict() {
+        assert_eq!(
+            resolve(&[(Drive::Full, Signal::High), (Drive::Full, Signal::Low)]),
+            Signal::Conflict
+        );
+        assert_eq!(
+            resolve(&[(Drive::Weak, Signal::High), (Drive::Weak, Signal::Low)]),
+            Signal::Conflict
+        );
+        assert_eq!(
+            resolve(&[(Drive::Full, Signal::High), (Drive::Full, Signal::High)]),
+            Signal::High
+        );
+    }
+
+    #[test]
+    fn pull_up_and_open_drain_outputs_form_a_wired_and() {
+        let pull_up = (
+            output_drive_strength(Some(&OutputDrive::Weak), &Signal::High),
+            Signal::High,
+        );
+        let open_drain = |signal: Signal| {
+            (
+                output_drive_strength(Some(&OutputDrive::OpenDrain), &signal),
+                signal,
+            )
+        };
+
+        assert_eq!(
+            resolve(&[
+                pull_up.clone(),
+                open_drain(Signal::High),
+                open_drain(Signal::High)
+            ]),
+            Signal::High
+        );
+        assert_eq!(
+            resolve(&[pull_up, open_drain(Signal::High), open_drain(Signal::Low)]),
+            Signal::Low
+        );
+    }
+
+    #[test]
+    fn undriven_nets_are_low() {
+        assert_eq!(resolve(&[]), UNDRIVEN_SIGNAL);
+        assert_eq!(resolve(&[(Drive::None, Signal::High)]), Signal::Low);
+        assert_eq!(
+            resolve(&[(
+                output_drive_strength(Some(&OutputDrive::OpenDrain), &Signal::High),
+                Signal::High
+            )]),
+            Signal::Low
+        );
     }
 }
@@ -1,92 +1,636 @@
-use bevy::{color::palettes::css::GRAY, prelude::*, text::FontSmoothing};
+use std::{collections::HashMap, path::PathBuf};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
 
 use crate::{
-    assets::common_assets::CommonAssets,
-    designer::{devices::device::DeviceIds, position::Position},
-    events::SpawnDeviceEvent,
+    designer::{
+        cursor::Cursor,
+        devices::{
+            annotation::{NamedFrame, StickyNote, TextAnnotation},
+            binary_io::{BinaryDisplay, BinarySwitch, PushButton},
+            clock::Clock,
+            device::{Device, DeviceCategory, DeviceIds, DeviceSerializers},
+            displays::{HexDigitDisplay, Led, LedMatrix, SevenSegmentDisplay},
+            generic_chip::calculate_chip_extents,
+            net_label::NetLabel,
+        },
+        model::ModelRegistry,
+        pin::PinModelCollection,
+        position::Position,
+        render_settings::CircuitBoardRenderingSettings,
+        save_management::library::ComponentLibraries,
+    },
+    events::{SpawnDeviceEvent, SpawnLibraryPartEvent},
 };
 
-#[derive(Component)]
-pub struct ChipSelector;
+const PREVIEW_SIZE: f32 = 40.0;
+
+/// Pin labels and look of a device, used to draw its preview in the chip selector.
+#[derive(Default)]
+pub struct DevicePreview {
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+    pub shape: PreviewShape,
+}
+
+/// Simplified look of the board view of a device, so the entries can be told apart at a glance.
+#[derive(Default, Clone, Copy, PartialEq, Debug)]
+pub enum PreviewShape {
+    /// Body with inputs on the left and outputs on the right.
+    #[default]
+    Chip,
+    Switch,
+    PushButton,
+    BinaryDisplay,
+    Clock,
+    NetLabel,
+    SegmentDisplay,
+    Led,
+    LedMatrix {
+        rows: u32,
+        columns: u32,
+    },
+    Text,
+    StickyNote,
+    Frame,
+}
+
+impl PreviewShape {
+    fn of_device(device_id: &str, device: EntityRef) -> Self {
+        if let Some(led_matrix) = device.get::<LedMatrix>() {
+            return PreviewShape::LedMatrix {
+                rows: led_matrix.rows,
+                columns: led_matrix.columns,
+            };
+        }
+
+        let shapes = [
+            (BinarySwitch::device_id(), PreviewShape::Switch),
+            (PushButton::device_id(), PreviewShape::PushButton),
+            (BinaryDisplay::device_id(), PreviewShape::BinaryDisplay),
+            (Clock::device_id(), PreviewShape::Clock),
+            (NetLabel::device_id(), PreviewShape::NetLabel),
+            (
+                SevenSegmentDisplay::device_id(),
+                PreviewShape::SegmentDisplay,
+            ),
+            (HexDigitDisplay::device_id(), PreviewShape::SegmentDisplay),
+            (Led::device_id(), PreviewShape::Led),
+            (TextAnnotation::device_id(), PreviewShape::Text),
+            (StickyNote::device_id(), PreviewShape::StickyNote),
+            (NamedFrame::device_id(), PreviewShape::Frame),
+        ];
+
+        shapes
+            .into_iter()
+            .find(|(shape_device_id, _)| *shape_device_id == device_id)
+            .map(|(_, shape)| shape)
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DevicePreviews(pub HashMap<String, DevicePreview>);
 
-#[derive(Component)]
-pub struct ChipButton;
+#[derive(Resource, Default)]
+pub struct ChipSelectorSearch(pub String);
 
-pub fn spawn_chip_selector(
-    mut commands: Commands,
-    common_assets: Res<CommonAssets>,
-    q_device_ids: Res<DeviceIds>,
+/// What an entry of the chip selector spawns.
+enum ChipKind {
+    Device(String),
+    LibraryPart { library: PathBuf, part: String },
+}
+
+struct ChipEntry {
+    name: String,
+    category: DeviceCategory,
+    /// Name of the library the part belongs to.
+    group: Option<String>,
+    kind: ChipKind,
+    /// Shown when hovering the entry.
+    description: String,
+}
+
+/// Spawns every registered device once in a separate world to read its pins for the previews.
+pub fn build_device_previews(world: &mut World) {
+    let mut preview_world = World::new();
+    preview_world.init_resource::<ModelRegistry>();
+
+    let previews = world
+        .resource::<DeviceSerializers>()
+        .0
+        .iter()
+        .map(|(device_id, serializer)| {
+            let entity = (serializer.spawn)(&mut preview_world, Position::ZERO);
+
+            let mut preview = preview_world
+                .get::<PinModelCollection>(entity)
+                .map(|pin_model_collection| DevicePreview {
+                    inputs: pin_model_collection
+                        .iter_inputs()
+                        .map(|pin_model| pin_model.label.clone())
+                        .collect(),
+                    outputs: pin_model_collection
+                        .iter_outputs()
+                        .map(|pin_model| pin_model.label.clone())
+                        .collect(),
+                    ..default()
+                })
+                .unwrap_or_default();
+            preview.shape = PreviewShape::of_device(device_id, preview_world.entity(entity));
+
+            (device_id.clone(), preview)
+        })
+        .collect();
+
+    world.insert_resource(DevicePreviews(previews));
+}
+
+/// Palette on the right side with all devices and library parts grouped by category.
+/// Entries can be clicked to spawn them in the middle of the view or dragged onto the board.
+#[allow(clippy::too_many_arguments)]
+pub fn show_chip_selector(
+    mut contexts: EguiContexts,
+    mut search: ResMut<ChipSelectorSearch>,
+    device_ids: Res<DeviceIds>,
+    device_previews: Res<DevicePreviews>,
+    component_libraries: Res<ComponentLibraries>,
+    render_settings: Res<CircuitBoardRenderingSettings>,
+    q_cursor: Query<&Transform, With<Cursor>>,
+    q_camera: Query<&Transform, With<Camera2d>>,
+    mut spawn_ev_writer: EventWriter<SpawnDeviceEvent>,
+    mut spawn_part_ev_writer: EventWriter<SpawnLibraryPartEvent>,
 ) {
-    commands
-        .spawn((
-            ChipSelector,
-            Node {
-                width: Val::Vw(20.0),
-                height: Val::Vh(100.0),
-                justify_self: JustifySelf::End,
-                justify_content: JustifyContent::Center,
-                flex_direction: FlexDirection::Column,
-                align_items: AlignItems::Center,
-                border: UiRect::left(Val::Px(2.0)),
-                ..default()
-            },
-            BackgroundColor(Color::WHITE),
-            BorderColor(Color::BLACK),
-        ))
-        .with_children(|cs| {
-            for device_id in q_device_ids.devices.iter() {
-                cs.spawn((
-                    ChipButton,
-                    Button,
-                    Node {
-                        width: Val::Percent(100.0),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..default()
-                    },
-                    BackgroundColor(Color::WHITE),
-                ))
-                .with_children(|b| {
-                    b.spawn((
-                        Text::new(device_id),
-                        TextFont {
-                            font: common_assets.font.clone(),
-                            font_size: 20.0,
-                            font_smoothing: FontSmoothing::None,
-                        },
-                        TextColor(Color::BLACK),
-                    ));
-                });
-            }
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    let cursor_position = q_cursor
+        .get_single()
+        .map(|transform| transform.translation.truncate())
+        .unwrap_or_default();
+    let view_center = q_camera
+        .get_single()
+        .map(|transform| transform.translation.truncate())
+        .unwrap_or_default();
+
+    let entries = collect_chip_entries(&device_ids, &device_previews, &component_libraries);
+    let no_preview = DevicePreview::default();
+
+    egui::SidePanel::right("chip_selector")
+        .default_width(220.0)
+        .show(ctx, |ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut search.0)
+                    .hint_text("Search...")
+                    .desired_width(f32::INFINITY),
+            );
+
+            let searching = !search.0.trim().is_empty();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for category in DeviceCategory::ALL {
+                    let matches: Vec<&ChipEntry> = entries
+                        .iter()
+                        .filter(|entry| entry.category == category)
+                        .filter(|entry| fuzzy_match(&search.0, &entry.name))
+                        .collect();
+
+                    if matches.is_empty() {
+                        continue;
+                    }
+
+                    egui::CollapsingHeader::new(category.name())
+                        .default_open(true)
+                        .open(searching.then_some(true))
+                        .show(ui, |ui| {
+                            let mut group: Option<&String> = None;
+
+                            for entry in matches {
+                                if entry.group.is_some() && entry.group.as_ref() != group {
+                                    group = entry.group.as_ref();
+                                    ui.weak(group.unwrap());
+                                }
+
+                                let preview = match &entry.kind {
+                                    ChipKind::Device(device_id) => {
+                                        device_previews.0.get(device_id).unwrap_or(&no_preview)
+                                    }
+                                    ChipKind::LibraryPart { .. } => &no_preview,
+                                };
+
+                                let response =
+                                    show_chip_entry(ui, entry, preview, &render_settings);
+
+                                // dragging spawns at the cursor and keeps dragging on the board,
+                                // clicking spawns in the middle of the view
+                                let (position, init_drag) = if response.drag_started() {
+                                    (cursor_position, true)
+                                } else if response.clicked() {
+                                    (view_center, false)
+                                } else {
+                                    continue;
+                                };
+
+                                match &entry.kind {
+                                    ChipKind::Device(device_id) => {
+                                        spawn_ev_writer.send(SpawnDeviceEvent {
+                                            device_id: device_id.clone(),
+                                            position: Position(position),
+                                            init_drag,
+                                        });
+                                    }
+                                    ChipKind::LibraryPart { library, part } => {
+                                        spawn_part_ev_writer.send(SpawnLibraryPartEvent {
+                                            library: library.clone(),
+                                            part: part.clone(),
+                                            position: Position(position),
+                                            init_drag,
+                                        });
+                                    }
+                                }
+                            }
+                        });
+                }
+            });
         });
 }
 
-#[allow(clippy::type_complexity)]
-pub fn chip_selector_button_interact(
-    mut q_buttons: Query<
-        (&Interaction, &mut BackgroundColor, &Children),
-        (Changed<Interaction>, With<ChipButton>),
-    >,
-    q_texts: Query<&Text>,
-    mut spawn_ev_writer: EventWriter<SpawnDeviceEvent>,
+fn collect_chip_entries(
+    device_ids: &DeviceIds,
+    device_previews: &DevicePreviews,
+    component_libraries: &ComponentLibraries,
+) -> Vec<ChipEntry> {
+    let devices = device_ids.devices.iter().map(|device_id| {
+        let description = device_previews
+            .0
+            .get(device_id)
+            .map(|preview| {
+                format!(
+                    "Inputs: {}\nOutputs: {}",
+                    preview.inputs.join(", "),
+                    preview.outputs.join(", ")
+                )
+            })
+            .unwrap_or_default();
+
+        ChipEntry {
+            name: device_id.clone(),
+            category: device_ids
+                .categories
+                .get(device_id)
+                .copied()
+                .unwrap_or(DeviceCategory::Custom),
+            group: None,
+            kind: ChipKind::Device(device_id.clone()),
+            description,
+        }
+    });
+
+    let library_parts = component_libraries.libraries.iter().flat_map(|library| {
+        library.file.parts.iter().map(|part| ChipEntry {
+            name: part.name.clone(),
+            category: DeviceCategory::Custom,
+            group: Some(library.file.name.clone()),
+            kind: ChipKind::LibraryPart {
+                library: library.path.clone(),
+                part: part.name.clone(),
+            },
+            description: format!(
+                "{} devices, {} wires",
+                part.board.devices.len(),
+                part.board.wires.len()
+            ),
+        })
+    });
+
+    devices.chain(library_parts).collect()
+}
+
+/// Draws a preview of the chip next to its name and returns the response of the whole entry.
+fn show_chip_entry(
+    ui: &mut egui::Ui,
+    entry: &ChipEntry,
+    preview: &DevicePreview,
+    render_settings: &CircuitBoardRenderingSettings,
+) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), PREVIEW_SIZE + 4.0),
+        egui::Sense::click_and_drag(),
+    );
+
+    let painter = ui.painter_at(rect);
+    if response.hovered() || response.dragged() {
+        painter.rect_filled(rect, 2.0, ui.visuals().widgets.hovered.bg_fill);
+    }
+
+    let preview_rect = egui::Rect::from_min_size(
+        rect.min + egui::vec2(2.0, 2.0),
+        egui::vec2(PREVIEW_SIZE, PREVIEW_SIZE),
+    );
+    draw_chip_preview(&painter, preview_rect, preview, render_settings);
+
+    painter.text(
+        egui::pos2(preview_rect.max.x + 8.0, rect.center().y),
+        egui::Align2::LEFT_CENTER,
+        &entry.name,
+        egui::FontId::proportional(14.0),
+        ui.visuals().text_color(),
+    );
+
+    response
+        .on_hover_text(&entry.description)
+        .on_hover_cursor(egui::CursorIcon::Grab)
+}
+
+/// Draws a simplified preview of the board view, scaled to fit the rect.
+/// Chips and displays lay out their pins like on the board, smaller devices only show their shape
+/// and colors. Lights are drawn switched on, so they stand out at this size.
+fn draw_chip_preview(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    preview: &DevicePreview,
+    render_settings: &CircuitBoardRenderingSettings,
 ) {
-    for (interaction, mut background_color, children) in q_buttons.iter_mut() {
-        let button_text = q_texts.get(*children.first().unwrap()).unwrap();
+    let num_inputs = preview.inputs.len();
+    let num_outputs = preview.outputs.len();
+    let chip_extents = calculate_chip_extents(render_settings, num_inputs, num_outputs);
+
+    let extents = match preview.shape {
+        PreviewShape::Chip | PreviewShape::SegmentDisplay | PreviewShape::LedMatrix { .. } => {
+            chip_extents
+        }
+        PreviewShape::Switch | PreviewShape::PushButton => render_settings.binary_switch_extents,
+        PreviewShape::BinaryDisplay | PreviewShape::Led => render_settings.binary_display_extents,
+        PreviewShape::Clock => render_settings.clock_extents,
+        PreviewShape::NetLabel => render_settings.net_label_extents,
+        PreviewShape::Text => Vec2::new(2.0, 1.0),
+        PreviewShape::StickyNote => render_settings.sticky_note_extents,
+        PreviewShape::Frame => Vec2::new(4.0, 3.0),
+    };
+
+    let scale = (rect.width() * 0.8) / extents.x.max(extents.y);
+    let body = egui::Rect::from_center_size(
+        rect.center(),
+        egui::vec2(extents.x * scale, extents.y * scale),
+    );
+
+    let rounding = render_settings.device_border_radius * scale;
+    let stroke = egui::Stroke::new(1.0, to_color32(render_settings.device_stroke_color));
+    let light_on = to_color32(render_settings.segment_on_color);
+    let pin_radius = (render_settings.chip_pin_radius * scale).max(1.5);
+    let pin_color = to_color32(render_settings.pin_color);
+    let pin_gap = render_settings.chip_pin_gap * scale;
 
-        match *interaction {
-            Interaction::None => {
-                background_color.0 = Color::WHITE;
+    match preview.shape {
+        PreviewShape::Chip => {
+            painter.rect(
+                body,
+                rounding,
+                to_color32(render_settings.chip_color),
+                stroke,
+            );
+            draw_chip_pins(painter, body, preview, pin_gap, pin_radius, pin_color);
+        }
+        PreviewShape::SegmentDisplay => {
+            painter.rect(
+                body,
+                rounding,
+                to_color32(render_settings.display_color),
+                stroke,
+            );
+            draw_chip_pins(painter, body, preview, pin_gap, pin_radius, pin_color);
+
+            // an 8 made of the seven segments
+            let digit = egui::Rect::from_center_size(
+                body.center(),
+                egui::vec2(body.width() * 0.3, body.height() * 0.6),
+            );
+            let segment_stroke = egui::Stroke::new((body.width() * 0.06).max(1.0), light_on);
+            let (left, right) = (digit.left(), digit.right());
+            let (top, middle, bottom) = (digit.top(), digit.center().y, digit.bottom());
+
+            for (from, to) in [
+                ((left, top), (right, top)),
+                ((left, middle), (right, middle)),
+                ((left, bottom), (right, bottom)),
+                ((left, top), (left, bottom)),
+                ((right, top), (right, bottom)),
+            ] {
+                painter.line_segment(
+                    [egui::pos2(from.0, from.1), egui::pos2(to.0, to.1)],
+                    segment_stroke,
+                );
             }
-            Interaction::Hovered => background_color.0 = GRAY.into(),
-            Interaction::Pressed => {
-                let device_id = button_text.0.clone();
-
-                spawn_ev_writer.send(SpawnDeviceEvent {
-                    device_id,
-                    position: Position::ZERO,
-                    init_drag: true,
-                });
+        }
+        PreviewShape::LedMatrix { rows, columns } => {
+            painter.rect(
+                body,
+                rounding,
+                to_color32(render_settings.display_color),
+                stroke,
+            );
+            draw_chip_pins(painter, body, preview, pin_gap, pin_radius, pin_color);
+
+            let grid = egui::Rect::from_center_size(
+                body.center(),
+                egui::vec2(body.width() * 0.6, body.height() * 0.8),
+            );
+            let spacing = egui::vec2(
+                grid.width() / columns.max(1) as f32,
+                grid.height() / rows.max(1) as f32,
+            );
+            let radius = (spacing.x.min(spacing.y) * 0.35).max(0.5);
+
+            for row in 0..rows {
+                for column in 0..columns {
+                    let center = grid.min
+                        + egui::vec2(
+                            (column as f32 + 0.5) * spacing.x,
+                            (row as f32 + 0.5) * spacing.y,
+                        );
+                    painter.circle_filled(center, radius, light_on);
+                }
+            }
+        }
+        PreviewShape::Switch | PreviewShape::PushButton => {
+            painter.rect(
+                body,
+                rounding,
+                to_color32(render_settings.binary_io_color),
+                stroke,
+            );
+
+            let knob_center = egui::pos2(body.left() + body.width() / 4.0, body.center().y);
+            match preview.shape {
+                PreviewShape::Switch => painter.rect_filled(
+                    egui::Rect::from_center_size(
+                        knob_center,
+                        egui::vec2(body.width() / 4.0, body.height() / 2.0),
+                    ),
+                    rounding / 2.0,
+                    stroke.color,
+                ),
+                _ => painter.circle_filled(
+                    knob_center,
+                    body.height() * 0.35,
+                    to_color32(render_settings.push_button_color),
+                ),
+            };
+
+            painter.circle_filled(body.right_center(), pin_radius, pin_color);
+        }
+        PreviewShape::BinaryDisplay => {
+            painter.rect(
+                body,
+                rounding,
+                to_color32(render_settings.binary_io_color),
+                stroke,
+            );
+            painter.text(
+                body.center(),
+                egui::Align2::CENTER_CENTER,
+                "1",
+                egui::FontId::monospace(body.height() * 0.6),
+                stroke.color,
+            );
+            painter.circle_filled(body.left_center(), pin_radius, pin_color);
+        }
+        PreviewShape::Led => {
+            painter.rect(
+                body,
+                rounding,
+                to_color32(render_settings.display_color),
+                stroke,
+            );
+            painter.circle_filled(body.center(), render_settings.led_radius * scale, light_on);
+            painter.circle_filled(body.left_center(), pin_radius, pin_color);
+        }
+        PreviewShape::Clock => {
+            painter.rect(
+                body,
+                rounding,
+                to_color32(render_settings.clock_color),
+                stroke,
+            );
+
+            // one period of a square wave
+            let wave = body.shrink(body.width() * 0.25);
+            let points = [
+                wave.left_bottom(),
+                egui::pos2(wave.left(), wave.top()),
+                egui::pos2(wave.center().x, wave.top()),
+                egui::pos2(wave.center().x, wave.bottom()),
+                wave.right_bottom(),
+                wave.right_top(),
+            ];
+            painter.add(egui::Shape::line(points.to_vec(), stroke));
+
+            painter.circle_filled(body.right_center(), pin_radius, pin_color);
+        }
+        PreviewShape::NetLabel => {
+            // a tag pointing towards the pin
+            let tip = body.height() / 2.0;
+            let points = vec![
+                body.left_center(),
+                egui::pos2(body.left() + tip, body.top()),
+                body.right_top(),
+                body.right_bottom(),
+                egui::pos2(body.left() + tip, body.bottom()),
+            ];
+            painter.add(egui::Shape::convex_polygon(
+                points,
+                to_color32(render_settings.net_label_color),
+                stroke,
+            ));
+            painter.circle_filled(body.left_center(), pin_radius, pin_color);
+        }
+        PreviewShape::Text => {
+            painter.text(
+                body.center(),
+                egui::Align2::CENTER_CENTER,
+                "Abc",
+                egui::FontId::proportional(body.height() * 0.8),
+                egui::Color32::BLACK,
+            );
+        }
+        PreviewShape::StickyNote => {
+            painter.rect(
+                body,
+                0.0,
+                to_color32(render_settings.sticky_note_color),
+                stroke,
+            );
+
+            for line in 1..4 {
+                let y = body.top() + body.height() * line as f32 / 4.0;
+                painter.line_segment(
+                    [
+                        egui::pos2(body.left() + body.width() * 0.15, y),
+                        egui::pos2(body.right() - body.width() * 0.15, y),
+                    ],
+                    egui::Stroke::new(1.0, stroke.color.gamma_multiply(0.4)),
+                );
             }
         }
+        PreviewShape::Frame => {
+            let frame_stroke =
+                egui::Stroke::new(1.0, to_color32(render_settings.frame_stroke_color));
+            painter.rect(
+                body,
+                0.0,
+                to_color32(render_settings.frame_color),
+                frame_stroke,
+            );
+
+            let title_height = body.height() * 0.2;
+            painter.rect_filled(
+                egui::Rect::from_min_size(body.min, egui::vec2(body.width(), title_height)),
+                0.0,
+                frame_stroke.color,
+            );
+        }
+    }
+}
+
+/// Draws the pins along the sides of the body the same way the generic chip view lays them out.
+fn draw_chip_pins(
+    painter: &egui::Painter,
+    body: egui::Rect,
+    preview: &DevicePreview,
+    pin_gap: f32,
+    pin_radius: f32,
+    pin_color: egui::Color32,
+) {
+    let pin_y = |i: usize| body.max.y - ((i as f32 + 0.75) * pin_gap);
+
+    for i in 0..preview.inputs.len() {
+        painter.circle_filled(egui::pos2(body.min.x, pin_y(i)), pin_radius, pin_color);
+    }
+
+    // a single output is centered, like on the board
+    for i in 0..preview.outputs.len() {
+        let y = match preview.outputs.len() {
+            1 => body.center().y,
+            _ => pin_y(i),
+        };
+        painter.circle_filled(egui::pos2(body.max.x, y), pin_radius, pin_color);
     }
 }
+
+fn to_color32(color: Color) -> egui::Color32 {
+    let [r, g, b, a] = color.to_srgba().to_u8_array();
+    egui::Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
+/// Checks if all characters of the query appear in the name in the same order, ignoring case.
+fn fuzzy_match(query: &str, name: &str) -> bool {
+    let mut name_chars = name.chars().map(|c| c.to_ascii_lowercase());
+
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .all(|query_char| name_chars.any(|name_char| name_char == query_char))
+}
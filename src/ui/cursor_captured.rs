@@ -1,8 +1,13 @@
 use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::EguiContexts;
 
 #[derive(Resource, PartialEq, Eq)]
 pub struct IsCursorCaptured(pub bool);
 
+/// Whether a text field currently has keyboard focus and keybindings should be ignored.
+#[derive(Resource, PartialEq, Eq)]
+pub struct IsKeyboardCaptured(pub bool);
+
 // #[derive(Component)]
 // pub struct NoCursorCapture;
 
@@ -31,3 +36,20 @@ pub fn check_cursor_captured(
         })
         .unwrap_or(false);
 }
+
+/// Also treats the cursor as captured while it is over an egui window.
+pub fn check_egui_captured(
+    mut contexts: EguiContexts,
+    mut is_cursor_captured: ResMut<IsCursorCaptured>,
+    mut is_keyboard_captured: ResMut<IsKeyboardCaptured>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    if ctx.is_pointer_over_area() || ctx.wants_pointer_input() {
+        is_cursor_captured.0 = true;
+    }
+
+    is_keyboard_captured.0 = ctx.wants_keyboard_input();
+}
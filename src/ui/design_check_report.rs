@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{designer::design_check::DesignCheckReport, events::FocusEntityEvent};
+
+/// Lists the issues of the last design check. Clicking an issue focuses the offending entity.
+pub fn show_design_check_report(
+    mut contexts: EguiContexts,
+    mut report: ResMut<DesignCheckReport>,
+    mut focus_ev_writer: EventWriter<FocusEntityEvent>,
+) {
+    if !report.open {
+        return;
+    }
+
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    let mut open = true;
+
+    egui::Window::new("Design Check")
+        .open(&mut open)
+        .show(ctx, |ui| {
+            if report.issues.is_empty() {
+                ui.label("No problems found.");
+                return;
+            }
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for issue in report.issues.iter() {
+                    if ui.link(&issue.message).clicked() {
+                        focus_ev_writer.send(FocusEntityEvent {
+                            entity: issue.entity,
+                            position: issue.position,
+                        });
+                    }
+                }
+            });
+        });
+
+    if !open {
+        report.open = false;
+    }
+}
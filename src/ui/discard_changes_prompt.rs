@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    designer::save_management::{DiscardChangesPrompt, PendingAction},
+    events::{CloseTabEvent, LoadRequestEvent, NewFileEvent, OpenRecentFileEvent},
+};
+
+/// Asks whether unsaved changes should be discarded and resends the pending action if so.
+pub fn show_discard_changes_prompt(
+    mut contexts: EguiContexts,
+    mut prompt: ResMut<DiscardChangesPrompt>,
+    mut new_file_ev_writer: EventWriter<NewFileEvent>,
+    mut load_request_ev_writer: EventWriter<LoadRequestEvent>,
+    mut open_recent_ev_writer: EventWriter<OpenRecentFileEvent>,
+    mut close_tab_ev_writer: EventWriter<CloseTabEvent>,
+) {
+    let Some(pending_action) = prompt.pending.clone() else {
+        return;
+    };
+
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Unsaved changes")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label("The board has unsaved changes. Discard them?");
+
+            ui.horizontal(|ui| {
+                if ui.button("Discard").clicked() {
+                    prompt.pending = None;
+                    prompt.confirmed = true;
+
+                    match pending_action.clone() {
+                        PendingAction::NewFile => {
+                            new_file_ev_writer.send(NewFileEvent);
+                        }
+                        PendingAction::Load => {
+                            load_request_ev_writer.send(LoadRequestEvent);
+                        }
+                        PendingAction::OpenRecent(path) => {
+                            open_recent_ev_writer.send(OpenRecentFileEvent { path });
+                        }
+                        PendingAction::CloseTab(index) => {
+                            close_tab_ev_writer.send(CloseTabEvent { index });
+                        }
+                    }
+                }
+
+                if ui.button("Cancel").clicked() {
+                    prompt.pending = None;
+                }
+            });
+        });
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    designer::save_management::library::ExportLibraryPrompt, events::ExportLibraryRequestEvent,
+};
+
+/// Asks for the part name before the selection is exported into a library file.
+pub fn show_export_library_prompt(
+    mut contexts: EguiContexts,
+    mut export_library_prompt: ResMut<ExportLibraryPrompt>,
+    mut export_ev_writer: EventWriter<ExportLibraryRequestEvent>,
+) {
+    if !export_library_prompt.open {
+        return;
+    }
+
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    let mut open = true;
+    egui::Window::new("Export selection to library")
+        .collapsible(false)
+        .resizable(false)
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Part name");
+                ui.text_edit_singleline(&mut export_library_prompt.part_name);
+            });
+
+            ui.label("Exporting into an existing library adds the part to it.");
+
+            let part_name = export_library_prompt.part_name.trim().to_string();
+            if ui
+                .add_enabled(!part_name.is_empty(), egui::Button::new("Export..."))
+                .clicked()
+            {
+                export_ev_writer.send(ExportLibraryRequestEvent { part_name });
+                export_library_prompt.open = false;
+            }
+        });
+
+    if !open {
+        export_library_prompt.open = false;
+    }
+}
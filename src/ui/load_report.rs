@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::designer::save_management::LoadReport;
+
+/// Tells the user why a board could not be loaded or which parts of it had to be repaired.
+pub fn show_load_report(mut contexts: EguiContexts, mut report: ResMut<LoadReport>) {
+    if !report.has_problems() {
+        return;
+    }
+
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    let title = match report.error {
+        Some(_) => "Load failed",
+        None => "Board repaired",
+    };
+
+    let mut open = true;
+
+    egui::Window::new(title)
+        .open(&mut open)
+        .collapsible(false)
+        .show(ctx, |ui| {
+            if let Some(path) = &report.path {
+                ui.label(path.display().to_string());
+                ui.separator();
+            }
+
+            match &report.error {
+                Some(error) => {
+                    ui.label("The board could not be loaded:");
+                    ui.label(error);
+                }
+                None => {
+                    ui.label("The board contained broken parts that were fixed:");
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        for repair in report.repairs.iter() {
+                            ui.label(repair);
+                        }
+                    });
+                }
+            }
+        });
+
+    if !open {
+        *report = LoadReport::default();
+    }
+}
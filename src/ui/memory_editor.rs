@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    designer::{
+        devices::{bit_width::BitWidth, memory::MemoryContents},
+        pin::PinModelCollection,
+        save_management::ActiveSaveFile,
+        selection::{Dragged, Selected},
+    },
+    events::LoadMemoryImageRequestEvent,
+};
+
+const WORDS_PER_ROW: usize = 8;
+
+/// Shows the contents of the selected memory device as a hex grid that can be edited.
+/// The grid updates while simulating and highlights the currently addressed word.
+#[allow(clippy::type_complexity)]
+pub fn show_memory_editor(
+    mut contexts: EguiContexts,
+    mut q_memories: Query<
+        (Entity, &mut MemoryContents, &BitWidth, &PinModelCollection),
+        (With<Selected>, Without<Dragged>),
+    >,
+    mut active_save_file: ResMut<ActiveSaveFile>,
+    mut load_ev_writer: EventWriter<LoadMemoryImageRequestEvent>,
+) {
+    let Ok((device_entity, mut memory_contents, bit_width, pin_model_collection)) =
+        q_memories.get_single_mut()
+    else {
+        return;
+    };
+
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    let active_address = pin_model_collection
+        .read_bits("A", memory_contents.address_width)
+        .map(|address| address as usize);
+    let digits = MemoryContents::hex_digits(*bit_width);
+    let address_digits = (memory_contents.address_width as usize).div_ceil(4).max(2);
+    let num_rows = memory_contents.words.len().div_ceil(WORDS_PER_ROW);
+
+    egui::Window::new("Memory")
+        .default_height(300.0)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Load file...").clicked() {
+                    load_ev_writer.send(LoadMemoryImageRequestEvent {
+                        device: device_entity,
+                    });
+                }
+
+                if ui.button("Clear").clicked() {
+                    memory_contents.words.fill(0);
+                    active_save_file.modified = true;
+                }
+            });
+
+            ui.separator();
+
+            let row_height = ui.spacing().interact_size.y;
+            egui::ScrollArea::vertical().show_rows(ui, row_height, num_rows, |ui, rows| {
+                egui::Grid::new("memory_words")
+                    .num_columns(WORDS_PER_ROW + 1)
+                    .show(ui, |ui| {
+                        for row in rows {
+                            let row_start = row * WORDS_PER_ROW;
+                            ui.monospace(format!("{:0width$x}", row_start, width = address_digits));
+
+                            for address in row_start..row_start + WORDS_PER_ROW {
+                                let Some(&word) = memory_contents.words.get(address) else {
+                                    break;
+                                };
+
+                                let mut value = word;
+                                let mut drag_value = egui::DragValue::new(&mut value)
+                                    .hexadecimal(digits, false, false)
+                                    .range(0..=bit_width.mask());
+                                if active_address == Some(address) {
+                                    drag_value = drag_value.prefix(">");
+                                }
+
+                                if ui.add(drag_value).changed() && value != word {
+                                    memory_contents.words[address] = value;
+                                    active_save_file.modified = true;
+                                }
+                            }
+
+                            ui.end_row();
+                        }
+                    });
+            });
+        });
+}
@@ -0,0 +1,106 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    designer::save_management::{
+        library::ExportLibraryPrompt, recent_files::RecentFiles, tabs::BoardTabs,
+    },
+    events::{
+        CloseTabEvent, ImportLibraryRequestEvent, LoadRequestEvent, NewFileEvent, NewTabEvent,
+        OpenRecentFileEvent, SaveAsRequestEvent, SaveRequestEvent,
+    },
+};
+
+#[derive(SystemParam)]
+pub struct FileMenuEvents<'w> {
+    new_file: EventWriter<'w, NewFileEvent>,
+    load_request: EventWriter<'w, LoadRequestEvent>,
+    save_request: EventWriter<'w, SaveRequestEvent>,
+    save_as_request: EventWriter<'w, SaveAsRequestEvent>,
+    open_recent: EventWriter<'w, OpenRecentFileEvent>,
+    import_library_request: EventWriter<'w, ImportLibraryRequestEvent>,
+    new_tab: EventWriter<'w, NewTabEvent>,
+    close_tab: EventWriter<'w, CloseTabEvent>,
+}
+
+/// Menu bar at the top of the window with the file actions, the recent files and the libraries.
+pub fn show_menu_bar(
+    mut contexts: EguiContexts,
+    recent_files: Res<RecentFiles>,
+    board_tabs: Res<BoardTabs>,
+    mut export_library_prompt: ResMut<ExportLibraryPrompt>,
+    mut events: FileMenuEvents,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    egui::TopBottomPanel::top("menu_bar").show(ctx, |ui| {
+        egui::menu::bar(ui, |ui| {
+            ui.menu_button("File", |ui| {
+                if ui.button("New").clicked() {
+                    events.new_file.send(NewFileEvent);
+                    ui.close_menu();
+                }
+
+                if ui.button("New Tab").clicked() {
+                    events.new_tab.send(NewTabEvent);
+                    ui.close_menu();
+                }
+
+                if ui.button("Close Tab").clicked() {
+                    events.close_tab.send(CloseTabEvent {
+                        index: board_tabs.active,
+                    });
+                    ui.close_menu();
+                }
+
+                if ui.button("Open...").clicked() {
+                    events.load_request.send(LoadRequestEvent);
+                    ui.close_menu();
+                }
+
+                ui.menu_button("Open Recent", |ui| {
+                    if recent_files.paths.is_empty() {
+                        ui.label("No recent files");
+                    }
+
+                    for path in recent_files.paths.iter() {
+                        if ui.button(path.display().to_string()).clicked() {
+                            events
+                                .open_recent
+                                .send(OpenRecentFileEvent { path: path.clone() });
+                            ui.close_menu();
+                        }
+                    }
+                });
+
+                ui.separator();
+
+                if ui.button("Save").clicked() {
+                    events.save_request.send(SaveRequestEvent);
+                    ui.close_menu();
+                }
+
+                if ui.button("Save As...").clicked() {
+                    events.save_as_request.send(SaveAsRequestEvent);
+                    ui.close_menu();
+                }
+
+                ui.separator();
+
+                if ui.button("Import Library...").clicked() {
+                    events
+                        .import_library_request
+                        .send(ImportLibraryRequestEvent);
+                    ui.close_menu();
+                }
+
+                if ui.button("Export Selection to Library...").clicked() {
+                    export_library_prompt.open = true;
+                    ui.close_menu();
+                }
+            });
+        });
+    });
+}
@@ -1,20 +1,65 @@
 use bevy::prelude::*;
+use bevy_egui::EguiPlugin;
 
 use self::{
-    chip_selector::{chip_selector_button_interact, spawn_chip_selector},
-    cursor_captured::{check_cursor_captured, IsCursorCaptured},
+    chip_selector::{build_device_previews, show_chip_selector, ChipSelectorSearch},
+    cursor_captured::{
+        check_cursor_captured, check_egui_captured, IsCursorCaptured, IsKeyboardCaptured,
+    },
+    design_check_report::show_design_check_report,
+    discard_changes_prompt::show_discard_changes_prompt,
+    export_library_prompt::show_export_library_prompt,
+    load_report::show_load_report,
+    memory_editor::show_memory_editor,
+    menu_bar::show_menu_bar,
+    peripherals::{show_keyboards, show_terminals},
+    properties::show_device_properties,
+    recovery_prompt::show_recovery_prompt,
+    tab_bar::show_tab_bar,
 };
 
 pub mod chip_selector;
 pub mod cursor_captured;
+pub mod design_check_report;
+pub mod discard_changes_prompt;
+pub mod export_library_prompt;
+pub mod load_report;
+pub mod memory_editor;
+pub mod menu_bar;
+pub mod peripherals;
+pub mod properties;
+pub mod recovery_prompt;
+pub mod tab_bar;
 
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
+        // the debug inspector might have added it already
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin);
+        }
+
         app.insert_resource(IsCursorCaptured(false))
-            .add_systems(Startup, spawn_chip_selector)
-            .add_systems(Update, check_cursor_captured)
-            .add_systems(Update, chip_selector_button_interact);
+            .insert_resource(IsKeyboardCaptured(false))
+            .init_resource::<ChipSelectorSearch>()
+            .add_systems(Startup, build_device_previews)
+            .add_systems(Update, (check_cursor_captured, check_egui_captured).chain())
+            .add_systems(
+                Update,
+                (
+                    // the top panels have to claim the full width before the side panel is added
+                    (show_menu_bar, show_tab_bar, show_chip_selector).chain(),
+                    show_device_properties,
+                    show_memory_editor,
+                    show_terminals,
+                    show_keyboards,
+                    show_design_check_report,
+                    show_load_report,
+                    show_discard_changes_prompt,
+                    show_recovery_prompt,
+                    show_export_library_prompt,
+                ),
+            );
     }
 }
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::designer::devices::peripherals::{KeyboardBuffer, TerminalOutput, BACKSPACE};
+
+/// Shows a window with the text of every terminal on the board.
+pub fn show_terminals(
+    mut contexts: EguiContexts,
+    mut q_terminals: Query<(Entity, &mut TerminalOutput)>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    for (device_entity, mut terminal_output) in q_terminals.iter_mut() {
+        egui::Window::new("Terminal")
+            .id(egui::Id::new(("terminal", device_entity)))
+            .default_size([320.0, 200.0])
+            .show(ctx, |ui| {
+                if ui.button("Clear").clicked() {
+                    terminal_output.text.clear();
+                }
+
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .stick_to_bottom(true)
+                    .auto_shrink(false)
+                    .show(ui, |ui| {
+                        ui.monospace(&terminal_output.text);
+                    });
+            });
+    }
+}
+
+/// Shows a window for every keyboard on the board, text typed into it is added to the keyboard's buffer.
+pub fn show_keyboards(
+    mut contexts: EguiContexts,
+    mut q_keyboards: Query<(Entity, &mut KeyboardBuffer)>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    for (device_entity, mut keyboard_buffer) in q_keyboards.iter_mut() {
+        egui::Window::new("Keyboard")
+            .id(egui::Id::new(("keyboard", device_entity)))
+            .resizable(false)
+            .show(ctx, |ui| {
+                // the typed text is moved into the buffer right away, so the field always stays empty
+                let mut typed = String::new();
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut typed)
+                        .hint_text("Type here...")
+                        .desired_width(200.0),
+                );
+
+                let mut bytes: Vec<u8> = typed
+                    .chars()
+                    .filter(char::is_ascii)
+                    .map(|c| c as u8)
+                    .collect();
+
+                // enter and backspace are handled by the text field, so they are forwarded manually
+                if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                    bytes.push(b'\n');
+                    response.request_focus();
+                }
+                if response.has_focus() && ui.input(|input| input.key_pressed(egui::Key::Backspace))
+                {
+                    bytes.push(BACKSPACE);
+                }
+
+                for byte in bytes {
+                    keyboard_buffer.push(byte);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} buffered", keyboard_buffer.characters.len()));
+
+                    if ui.button("Clear").clicked() {
+                        keyboard_buffer.characters.clear();
+                    }
+                });
+            });
+    }
+}
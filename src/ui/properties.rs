@@ -0,0 +1,94 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::{egui, EguiContext};
+
+use crate::designer::{
+    devices::device::DeviceModel,
+    save_management::format::{apply_device_properties, read_device_properties, PropertyValue},
+    selection::{Dragged, Selected},
+};
+
+/// Properties with their own editor, e.g. the contents of memories.
+const HIDDEN_PROPERTIES: [&str; 1] = ["contents"];
+
+/// Shows a window to edit the properties of the selected device.
+/// Edits are applied to the device in place and its view is rebuilt, so changes that add or remove pins are shown.
+pub fn show_device_properties(world: &mut World) {
+    let selected_devices: Vec<Entity> = world
+        .query_filtered::<Entity, (With<Selected>, With<DeviceModel>, Without<Dragged>)>()
+        .iter(world)
+        .collect();
+
+    let [device_entity] = selected_devices[..] else {
+        return;
+    };
+
+    let Some((device_id, mut properties)) = read_device_properties(world, device_entity) else {
+        return;
+    };
+
+    if properties.0.is_empty() {
+        return;
+    }
+
+    let Ok(mut egui_context) = world
+        .query_filtered::<&mut EguiContext, With<PrimaryWindow>>()
+        .get_single_mut(world)
+    else {
+        return;
+    };
+    let ctx = egui_context.get_mut().clone();
+
+    let mut changed = false;
+
+    egui::Window::new("Properties")
+        .resizable(false)
+        .show(&ctx, |ui| {
+            ui.strong(&device_id);
+
+            egui::Grid::new("device_properties")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    for (name, value) in properties
+                        .0
+                        .iter_mut()
+                        .filter(|(name, _)| !HIDDEN_PROPERTIES.contains(&name.as_str()))
+                    {
+                        ui.label(property_label(name));
+                        changed |= edit_property_value(ui, name, value);
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if !changed {
+        return;
+    }
+
+    apply_device_properties(world, device_entity, &properties);
+}
+
+/// Turns a property name like "select_width" into "Select width".
+fn property_label(name: &str) -> String {
+    let name = name.replace('_', " ");
+    let mut chars = name.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => name,
+    }
+}
+
+/// Returns true if the value was changed.
+fn edit_property_value(ui: &mut egui::Ui, name: &str, value: &mut PropertyValue) -> bool {
+    match value {
+        PropertyValue::Bool(value) => ui.checkbox(value, "").changed(),
+        PropertyValue::Int(value) => ui.add(egui::DragValue::new(value)).changed(),
+        PropertyValue::Float(value) => {
+            let speed = (value.abs() * 0.01).max(0.01);
+            ui.add(egui::DragValue::new(value).speed(speed)).changed()
+        }
+        // longer texts like the ones of notes can span multiple lines
+        PropertyValue::Text(value) if name == "text" => ui.text_edit_multiline(value).changed(),
+        PropertyValue::Text(value) => ui.text_edit_singleline(value).changed(),
+    }
+}
@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    designer::save_management::autosave::RecoveryPrompt,
+    events::{DiscardRecoveryEvent, RestoreRecoveryEvent},
+};
+
+/// Offers to restore the autosaves of sessions that did not exit cleanly.
+pub fn show_recovery_prompt(
+    mut contexts: EguiContexts,
+    recovery_prompt: Res<RecoveryPrompt>,
+    mut restore_ev_writer: EventWriter<RestoreRecoveryEvent>,
+    mut discard_ev_writer: EventWriter<DiscardRecoveryEvent>,
+) {
+    if !recovery_prompt.open {
+        return;
+    }
+
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    egui::Window::new("Restore unsaved work")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .show(ctx, |ui| {
+            ui.label(format!(
+                "Logics did not exit cleanly last time. Restore {} autosaved board(s) in new tabs?",
+                recovery_prompt.board_count()
+            ));
+
+            ui.horizontal(|ui| {
+                if ui.button("Restore").clicked() {
+                    restore_ev_writer.send(RestoreRecoveryEvent);
+                }
+
+                if ui.button("Discard").clicked() {
+                    discard_ev_writer.send(DiscardRecoveryEvent);
+                }
+            });
+        });
+}
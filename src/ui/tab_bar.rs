@@ -0,0 +1,62 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+
+use crate::{
+    designer::save_management::{tabs::BoardTabs, ActiveSaveFile},
+    events::{CloseTabEvent, NewTabEvent, SwitchTabEvent},
+};
+
+/// One tab per open board below the menu bar.
+pub fn show_tab_bar(
+    mut contexts: EguiContexts,
+    board_tabs: Res<BoardTabs>,
+    active_save_file: Res<ActiveSaveFile>,
+    mut new_tab_ev_writer: EventWriter<NewTabEvent>,
+    mut switch_tab_ev_writer: EventWriter<SwitchTabEvent>,
+    mut close_tab_ev_writer: EventWriter<CloseTabEvent>,
+) {
+    let Some(ctx) = contexts.try_ctx_mut() else {
+        return;
+    };
+
+    egui::TopBottomPanel::top("tab_bar").show(ctx, |ui| {
+        ui.horizontal(|ui| {
+            for (index, tab) in board_tabs.tabs.iter().enumerate() {
+                let is_active = index == board_tabs.active;
+
+                // the active tab is spawned, so its state is only up to date in the ActiveSaveFile
+                let modified = match is_active {
+                    true => active_save_file.modified,
+                    false => tab.modified,
+                };
+                let mut name = match is_active {
+                    true => active_save_file
+                        .path
+                        .as_ref()
+                        .and_then(|path| path.file_name())
+                        .and_then(|file_name| file_name.to_str())
+                        .map(String::from)
+                        .unwrap_or_else(|| tab.name()),
+                    false => tab.name(),
+                };
+                if modified {
+                    name.push('*');
+                }
+
+                if ui.selectable_label(is_active, name).clicked() {
+                    switch_tab_ev_writer.send(SwitchTabEvent { index });
+                }
+
+                if ui.small_button("x").on_hover_text("Close tab").clicked() {
+                    close_tab_ev_writer.send(CloseTabEvent { index });
+                }
+
+                ui.separator();
+            }
+
+            if ui.small_button("+").on_hover_text("New tab").clicked() {
+                new_tab_ev_writer.send(NewTabEvent);
+            }
+        });
+    });
+}